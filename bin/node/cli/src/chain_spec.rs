@@ -25,7 +25,8 @@ use node_runtime::{
 	AuthorityDiscoveryConfig, BabeConfig, BalancesConfig, ContractsConfig, CouncilConfig,
 	DemocracyConfig,GrandpaConfig, ImOnlineConfig, SessionConfig, SessionKeys, StakerStatus,
 	StakingConfig, ElectionsConfig, IndicesConfig, SocietyConfig, SudoConfig, SystemConfig,
-	TechnicalCommitteeConfig, wasm_binary_unwrap, TokensConfig, OrmlNFTConfig,
+	TechnicalCommitteeConfig, wasm_binary_unwrap, TokensConfig, OrmlNFTConfig, CurrencyRegistryConfig,
+	InitialCurrencies,
 };
 use node_runtime::Block;
 use node_runtime::constants::currency::*;
@@ -274,6 +275,7 @@ pub fn testnet_genesis(
 			authorities: vec![],
 		},
 		pallet_membership_Instance1: Default::default(),
+		pallet_membership_Instance2: Default::default(),
 		pallet_treasury: Default::default(),
 		pallet_society: SocietyConfig {
 			members: endowed_accounts.iter()
@@ -297,6 +299,9 @@ pub fn testnet_genesis(
 		},
 		orml_nft: OrmlNFTConfig { tokens: vec![] },
 		nftmart_nft: Default::default(),
+		nftmart_currency_registry: CurrencyRegistryConfig {
+			currencies: InitialCurrencies::get(),
+		},
 	}
 }
 
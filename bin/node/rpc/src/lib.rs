@@ -118,7 +118,8 @@ pub fn create_full<C, P, SC, B>(
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
 	C::Api: pallet_mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
-	C::Api: nftmart_rpc::NFTMartRuntimeApi<Block>,
+	C::Api: nftmart_rpc::NFTMartRuntimeApi<Block, AccountId>,
+	C::Api: orml_tokens_rpc::TokensRuntimeApi<Block, AccountId>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
@@ -131,6 +132,7 @@ pub fn create_full<C, P, SC, B>(
 	use pallet_mmr_rpc::{MmrApi, Mmr};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use nftmart_rpc::{NFTMart, NFTMartApi};
+	use orml_tokens_rpc::{Tokens, TokensApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
 	let FullDeps {
@@ -174,6 +176,9 @@ pub fn create_full<C, P, SC, B>(
 	io.extend_with(
 		NFTMartApi::to_delegate(NFTMart::new(client.clone()))
 	);
+	io.extend_with(
+		TokensApi::to_delegate(Tokens::new(client.clone()))
+	);
 	io.extend_with(
 		sc_consensus_babe_rpc::BabeApi::to_delegate(
 			BabeRpcHandler::new(
@@ -0,0 +1,36 @@
+;; Call the nftmart chain extension with the input of this contract and return its output.
+;; The input is the SCALE encoded `func_id: u32` followed by the input of the function.
+(module
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) len of input buffer
+	(data (i32.const 0) "\00\01")
+
+	;; [4, 260) buffer for input
+
+	;; [260, 264) len of output buffer
+	(data (i32.const 260) "\00\01")
+
+	;; [264, 520) buffer for output
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 4) (i32.const 0))
+
+		(drop (call $seal_call_chain_extension
+			(i32.load (i32.const 4))							;; func_id
+			(i32.const 8)										;; input_ptr
+			(i32.sub (i32.load (i32.const 0)) (i32.const 4))	;; input_len
+			(i32.const 264)										;; output_ptr
+			(i32.const 260)										;; output_len_ptr
+		))
+
+		(call $seal_return (i32.const 0) (i32.const 264) (i32.load (i32.const 260)))
+	)
+)
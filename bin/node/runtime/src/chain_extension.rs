@@ -0,0 +1,226 @@
+//! A chain extension giving contracts access to the NFTs of the marketplace.
+//!
+//! Inputs and outputs are SCALE encoded, the input is charged per byte as for `seal_input`.
+//! Calls are dispatched with their declared weight and through the `BaseCallFilter`,
+//! so paused calls are rejected as for extrinsics.
+//! A failed call traps the contract with its `DispatchError`.
+
+use codec::{Decode, Encode};
+use frame_support::{ensure, traits::Get, weights::GetDispatchInfo};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use sp_core::constants_types::{Balance, CategoryId, ClassId, CurrencyId, TokenId};
+use sp_runtime::{traits::Dispatchable, DispatchError};
+use sp_std::prelude::*;
+use crate::{AccountId, BlockNumber, Call, Origin, ProxyType, Runtime};
+
+/// `(ClassId, TokenId) -> Option<AccountId>`: the owner of a token.
+pub const OWNER_OF: u32 = 1001;
+/// `ClassId -> Option<ClassInfoOf<Runtime>>`: the info of a class.
+pub const CLASS_INFO: u32 = 1002;
+/// `(AccountId, ClassId, TokenId)`: transfer a token of the contract to an account.
+pub const TRANSFER: u32 = 1003;
+/// `(AccountId, ClassId, NFTMetadata, u32)`: mint tokens to an account.
+///
+/// The contract must be an admin of the class, a proxy of the class account of type `Any` without delay.
+pub const MINT: u32 = 1004;
+/// `(CurrencyId, Balance, CategoryId, ClassId, TokenId, Balance, BlockNumber)`: submit an order of the contract,
+/// with the arguments of `nftmart_nft::Call::submit_order` and no reserved takers.
+pub const SUBMIT_ORDER: u32 = 1005;
+/// `(ClassId, TokenId, Balance, AccountId)`: take an order from the contract,
+/// with the arguments of `nftmart_nft::Call::take_order`.
+pub const TAKE_ORDER: u32 = 1006;
+
+pub struct NftmartExtension;
+
+impl ChainExtension<Runtime> for NftmartExtension {
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let mut env = env.buf_in_buf_out();
+		let in_len = env.in_len();
+		let input_per_byte = env.ext().schedule().host_fn_weights.input_per_byte;
+		env.charge_weight(input_per_byte.saturating_mul(in_len.into()))?;
+		let contract = env.ext().address().clone();
+		match func_id {
+			OWNER_OF => {
+				let (class_id, token_id): (ClassId, TokenId) = env.read_as()?;
+				env.charge_weight(<Runtime as SysConfig>::DbWeight::get().reads(1))?;
+				let owner = orml_nft::Module::<Runtime>::tokens(class_id, token_id).map(|token| token.owner);
+				env.write(&owner.encode(), false, None)?;
+			},
+			CLASS_INFO => {
+				let class_id: ClassId = env.read_as()?;
+				env.charge_weight(<Runtime as SysConfig>::DbWeight::get().reads(1))?;
+				let class_info = orml_nft::Module::<Runtime>::classes(class_id);
+				env.write(&class_info.encode(), false, None)?;
+			},
+			TRANSFER => {
+				let (to, class_id, token_id): (AccountId, ClassId, TokenId) = env.read_as()?;
+				let call = Call::Nftmart(nftmart_nft::Call::transfer(to.into(), class_id, token_id));
+				env.charge_weight(call.get_dispatch_info().weight)?;
+				dispatch(call, contract)?;
+			},
+			MINT => {
+				let (to, class_id, metadata, quantity): (AccountId, ClassId, nftmart_nft::NFTMetadata, u32) = env.read_as()?;
+				env.charge_weight(<Runtime as SysConfig>::DbWeight::get().reads(2))?;
+				let owner = orml_nft::Module::<Runtime>::classes(class_id)
+					.ok_or(nftmart_nft::Error::<Runtime>::ClassIdNotFound)?
+					.owner;
+				ensure!(is_class_admin(&owner, &contract), nftmart_nft::Error::<Runtime>::NoPermission);
+				let call = Call::Nftmart(nftmart_nft::Call::mint(to.into(), class_id, metadata, quantity));
+				env.charge_weight(call.get_dispatch_info().weight)?;
+				dispatch(call, owner)?;
+			},
+			SUBMIT_ORDER => {
+				let (currency_id, price, category_id, class_id, token_id, deposit, deadline):
+					(CurrencyId, Balance, CategoryId, ClassId, TokenId, Balance, BlockNumber) = env.read_as()?;
+				let call = Call::Nftmart(nftmart_nft::Call::submit_order(
+					currency_id, price, category_id, class_id, token_id, deposit, deadline, Vec::new(),
+				));
+				env.charge_weight(call.get_dispatch_info().weight)?;
+				dispatch(call, contract)?;
+			},
+			TAKE_ORDER => {
+				let (class_id, token_id, price, order_owner): (ClassId, TokenId, Balance, AccountId) = env.read_as()?;
+				let call = Call::Nftmart(nftmart_nft::Call::take_order(class_id, token_id, price, order_owner));
+				env.charge_weight(call.get_dispatch_info().weight)?;
+				dispatch(call, contract)?;
+			},
+			_ => return Err(DispatchError::Other("Unknown nftmart chain extension function")),
+		}
+		Ok(RetVal::Converging(0))
+	}
+}
+
+/// Whether `who` may act as the class account `owner` for any call and at once.
+///
+/// The restricted or delayed proxies of `owner` can't mint through the extension,
+/// which dispatches as `owner` and would skip the filter and the announcement of the proxy.
+fn is_class_admin(owner: &AccountId, who: &AccountId) -> bool {
+	let (proxies, _) = pallet_proxy::Module::<Runtime>::proxies(owner);
+	proxies.iter().any(|proxy| {
+		// the fields of `ProxyDefinition` are private, read them through its encoding.
+		<(AccountId, ProxyType, BlockNumber)>::decode(&mut &proxy.encode()[..])
+			.map_or(false, |(delegate, proxy_type, delay)| {
+				delegate == *who && proxy_type == ProxyType::Any && delay == 0
+			})
+	})
+}
+
+/// Dispatch `call` signed by `who`.
+fn dispatch(call: Call, who: AccountId) -> Result<(), DispatchError> {
+	call.dispatch(Origin::signed(who)).map(|_| ()).map_err(|e| e.error)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{assert_noop, assert_ok};
+	use sp_core::constants_types::NATIVE_CURRENCY_ID;
+	use sp_runtime::traits::{AccountIdConversion, Hash};
+	use crate::{Balances, Contracts, Nftmart, System, constants::currency::DOLLARS};
+
+	const ALICE: AccountId = AccountId::new([1u8; 32]);
+	const BOB: AccountId = AccountId::new([2u8; 32]);
+	const CLASS_ID: ClassId = 0;
+	const GAS_LIMIT: u64 = 100_000_000_000;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 1_000 * DOLLARS), (BOB, 1_000 * DOLLARS)],
+		}.assimilate_storage(&mut t).unwrap();
+		pallet_contracts::GenesisConfig::<Runtime>::default().assimilate_storage(&mut t).unwrap();
+		nftmart_nft::GenesisConfig::<Runtime>::default().assimilate_storage(&mut t).unwrap();
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	/// Deploy the fixture passing its input through to the chain extension and returning the output.
+	fn deploy() -> AccountId {
+		let code = wat::parse_file("fixtures/nftmart_extension.wat").unwrap();
+		let code_hash = <Runtime as SysConfig>::Hashing::hash(&code);
+		let endowment = Contracts::subsistence_threshold() + 100 * DOLLARS;
+		assert_ok!(Contracts::instantiate_with_code(Origin::signed(ALICE), endowment, GAS_LIMIT, code, vec![], vec![]));
+		Contracts::contract_address(&ALICE, &code_hash, &[])
+	}
+
+	fn call(contract: &AccountId, func_id: u32, input: impl Encode) -> Result<Vec<u8>, DispatchError> {
+		let input = (func_id, input).encode();
+		Contracts::bare_call(BOB, contract.clone(), 0, GAS_LIMIT, input)
+			.exec_result
+			.map(|r| r.data)
+			.map_err(|e| e.error)
+	}
+
+	fn owner_of(contract: &AccountId, token_id: TokenId) -> Option<AccountId> {
+		let output = call(contract, OWNER_OF, (CLASS_ID, token_id)).unwrap();
+		Decode::decode(&mut &output[..]).unwrap()
+	}
+
+	#[test]
+	fn chain_extension_should_work() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy();
+			let permissions = nftmart_config::Permissions::all();
+			for who in &[ALICE, BOB, contract.clone()] {
+				assert_ok!(nftmart_config::Pallet::<Runtime>::add_whitelist(Origin::root(), who.clone(), permissions, None));
+			}
+			assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+			assert_ok!(Nftmart::create_class(
+				Origin::signed(ALICE), vec![1], vec![1], vec![1],
+				nftmart_nft::Properties(nftmart_nft::ClassProperty::Transferable.into()), None,
+			));
+			let class_account: AccountId = <Runtime as nftmart_nft::Config>::ModuleId::get().into_sub_account(CLASS_ID);
+			assert_ok!(Balances::transfer(Origin::signed(ALICE), class_account.clone().into(), 100 * DOLLARS));
+
+			let output = call(&contract, CLASS_INFO, CLASS_ID).unwrap();
+			let class_info: Option<orml_nft::ClassInfoOf<Runtime>> = Decode::decode(&mut &output[..]).unwrap();
+			assert_eq!(class_info.map(|info| info.owner), Some(class_account.clone()));
+
+			// only the admins of the class can mint.
+			assert_noop!(
+				call(&contract, MINT, (contract.clone(), CLASS_ID, vec![1u8], 2u32)),
+				nftmart_nft::Error::<Runtime>::NoPermission,
+			);
+			// nor the restricted or delayed proxies of the class account.
+			assert_ok!(pallet_proxy::Module::<Runtime>::add_proxy_delegate(&class_account, contract.clone(), ProxyType::NonTransfer, 0));
+			assert_ok!(pallet_proxy::Module::<Runtime>::add_proxy_delegate(&class_account, contract.clone(), ProxyType::Any, 1));
+			assert_noop!(
+				call(&contract, MINT, (contract.clone(), CLASS_ID, vec![1u8], 2u32)),
+				nftmart_nft::Error::<Runtime>::NoPermission,
+			);
+			assert_ok!(pallet_proxy::Module::<Runtime>::add_proxy_delegate(&class_account, contract.clone(), ProxyType::Any, 0));
+			assert_ok!(call(&contract, MINT, (contract.clone(), CLASS_ID, vec![1u8], 2u32)));
+			assert_eq!(owner_of(&contract, 0), Some(contract.clone()));
+			assert_eq!(owner_of(&contract, 2), None);
+
+			assert_ok!(call(&contract, TRANSFER, (BOB, CLASS_ID, 0 as TokenId)));
+			assert_eq!(owner_of(&contract, 0), Some(BOB));
+			assert_noop!(
+				call(&contract, TRANSFER, (BOB, CLASS_ID, 0 as TokenId)),
+				nftmart_nft::Error::<Runtime>::NoPermission,
+			);
+
+			let deposit = Nftmart::min_order_deposit();
+			assert_ok!(call(&contract, SUBMIT_ORDER, (
+				NATIVE_CURRENCY_ID, 10 * DOLLARS, 0 as CategoryId, CLASS_ID, 1 as TokenId, deposit, 10 as BlockNumber,
+			)));
+			assert_ok!(Nftmart::take_order(Origin::signed(BOB), CLASS_ID, 1, 10 * DOLLARS, contract.clone()));
+			assert_eq!(owner_of(&contract, 1), Some(BOB));
+
+			assert_ok!(Nftmart::submit_order(
+				Origin::signed(BOB), NATIVE_CURRENCY_ID, 5 * DOLLARS, 0, CLASS_ID, 0, deposit, 10, vec![],
+			));
+			assert_ok!(call(&contract, TAKE_ORDER, (CLASS_ID, 0 as TokenId, 5 * DOLLARS, BOB)));
+			assert_eq!(owner_of(&contract, 0), Some(contract.clone()));
+
+			assert_noop!(call(&contract, 0, ()), DispatchError::Other("Unknown nftmart chain extension function"));
+		});
+	}
+}
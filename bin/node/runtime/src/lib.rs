@@ -33,7 +33,7 @@ use frame_support::{
 	},
 	traits::{
 		Currency, Imbalance, KeyOwnerProofSystem, OnUnbalanced, Randomness, LockIdentifier,
-		U128CurrencyToVote,
+		U128CurrencyToVote, Filter,
 	},
 };
 use frame_system::{
@@ -61,7 +61,7 @@ use sp_runtime::curve::PiecewiseLinear;
 use sp_runtime::transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority};
 use sp_runtime::traits::{
 	self, BlakeTwo256, Block as BlockT, StaticLookup, SaturatedConversion, ConvertInto, OpaqueKeys,
-	NumberFor,
+	NumberFor, AccountIdConversion,
 };
 use sp_version::RuntimeVersion;
 #[cfg(any(feature = "std", test))]
@@ -88,6 +88,7 @@ pub use pallet_staking::StakerStatus;
 
 /// Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
+pub mod chain_extension;
 use impls::Author;
 
 /// Constant values used within the runtime.
@@ -116,10 +117,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 296,
+	spec_version: 297,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 3,
 };
 
 /// The BABE epoch configuration at genesis.
@@ -193,8 +194,19 @@ parameter_types! {
 
 const_assert!(NORMAL_DISPATCH_RATIO.deconstruct() >= AVERAGE_ON_INITIALIZE_RATIO.deconstruct());
 
+/// Rejects the paused marketplace calls, including the nested and proxied ones.
+pub struct BaseFilter;
+impl Filter<Call> for BaseFilter {
+	fn filter(c: &Call) -> bool {
+		match c {
+			Call::Nftmart(call) => !Nftmart::is_paused(call),
+			_ => true,
+		}
+	}
+}
+
 impl frame_system::Config for Runtime {
-	type BaseCallFilter = ();
+	type BaseCallFilter = BaseFilter;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
 	type DbWeight = RocksDbWeight;
@@ -394,7 +406,7 @@ parameter_types! {
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
+	type OnChargeTransaction = nftmart_fee_currency::MultiCurrencyAdapter<Runtime, CurrencyAdapter<Balances, DealWithFees>>;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate =
@@ -806,7 +818,7 @@ impl pallet_contracts::Config for Runtime {
 	type MaxValueSize = MaxValueSize;
 	type WeightPrice = pallet_transaction_payment::Module<Self>;
 	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
-	type ChainExtension = ();
+	type ChainExtension = chain_extension::NftmartExtension;
 	type DeletionQueueDepth = DeletionQueueDepth;
 	type DeletionWeightLimit = DeletionWeightLimit;
 	type MaxCodeSize = MaxCodeSize;
@@ -853,6 +865,7 @@ impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for R
 			frame_system::CheckNonce::<Runtime>::from(nonce),
 			frame_system::CheckWeight::<Runtime>::new(),
 			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			nftmart_nft::CheckPaused::<Runtime>::new(),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -1084,14 +1097,10 @@ impl pallet_gilt::Config for Runtime {
 	type WeightInfo = pallet_gilt::weights::SubstrateWeight<Runtime>;
 }
 
-orml_traits::parameter_type_with_key! {
-	pub ExistentialDeposits: |currency_id: sp_core::constants_types::CurrencyId| -> Balance {
-		if currency_id == &sp_core::constants_types::NATIVE_CURRENCY_ID {
-			ExistentialDeposit::get()
-		} else  {
-			Default::default()
-		}
-	};
+parameter_types! {
+	pub TreasuryAccount: AccountId = TreasuryModuleId::get().into_account();
+	pub const ExemptModuleAccounts: bool = true;
+	pub const MaxReserves: u32 = 50;
 }
 
 impl orml_tokens::Config for Runtime {
@@ -1100,8 +1109,11 @@ impl orml_tokens::Config for Runtime {
 	type Amount = sp_core::constants_types::Amount;
 	type CurrencyId = sp_core::constants_types::CurrencyId;
 	type WeightInfo = ();
-	type ExistentialDeposits = ExistentialDeposits;
-	type OnDust = ();
+	type ExistentialDeposits = CurrencyRegistry;
+	type OnDust = orml_tokens::TransferDust<Runtime, TreasuryAccount>;
+	type ExemptModuleAccounts = ExemptModuleAccounts;
+	type ReserveIdentifier = sp_core::constants_types::ReserveIdentifier;
+	type MaxReserves = MaxReserves;
 }
 
 parameter_types! {
@@ -1115,6 +1127,8 @@ impl orml_currencies::Config for Runtime {
 	type MultiCurrency = Tokens;
 	type NativeCurrency = AdaptedBasicCurrency;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type IssuerOrigin = EnsureRootOrHalfCouncil;
+	type IssuableCurrencies = CurrencyRegistry;
 	type WeightInfo = ();
 }
 
@@ -1126,25 +1140,156 @@ impl orml_nft::Config for Runtime {
 }
 
 parameter_types! {
-	pub const CreateClassDeposit: Balance = 2 * CENTS;
-	pub const CreateTokenDeposit: Balance = 1 * CENTS;
-	pub const MetaDataByteDeposit: Balance = 10 * MILLICENTS;
 	pub const NftModuleId: ModuleId = ModuleId(*b"nftmart*");
+	pub const MaxCategoryMetadataLen: u32 = 1024;
+	pub const MaxBundleSize: u32 = 64;
+	pub const MaxTakers: u32 = 16;
+	pub const MaxSalePhases: u32 = 8;
+	pub const MaxSaleAllowlistLen: u32 = 256;
+	pub const MaxClaimQuantity: u32 = 20;
+	pub const MaxSaleProofLen: u32 = 32;
+	pub const MaxBlindBoxItems: u32 = 1000;
+	pub const MaxBlindBoxItemLen: u32 = 256;
 }
 
 impl nftmart_nft::Config for Runtime {
 	type Event = Event;
-	type CreateClassDeposit = CreateClassDeposit;
-	type MetaDataByteDeposit = MetaDataByteDeposit;
-	type CreateTokenDeposit = CreateTokenDeposit;
 	type ModuleId = NftModuleId;
 	type Currency = Balances;
 	type MultiCurrency = Currencies;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type CategoryId = sp_core::constants_types::CategoryId;
+	type CategoryOrigin = EnsureRootOrHalfCouncil;
+	type MaxCategoryMetadataLen = MaxCategoryMetadataLen;
+	type MaxBundleSize = MaxBundleSize;
+	type MaxTakers = MaxTakers;
+	type MaxSalePhases = MaxSalePhases;
+	type MaxSaleAllowlistLen = MaxSaleAllowlistLen;
+	type MaxClaimQuantity = MaxClaimQuantity;
+	type MaxSaleProofLen = MaxSaleProofLen;
+	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
+	type MaxBlindBoxItems = MaxBlindBoxItems;
+	type MaxBlindBoxItemLen = MaxBlindBoxItemLen;
+	type CurrencyRegistry = CurrencyRegistry;
+	type Whitelist = nftmart_config::Pallet<Runtime>;
+	type MarketParameters = nftmart_config::Pallet<Runtime>;
+	type PausedCalls = nftmart_config::Pallet<Runtime>;
+}
+
+parameter_types! {
+	pub const NativeDecimals: u8 = 12;
+	pub const CurrencyStringLimit: u32 = 32;
+	pub InitialCurrencies: Vec<nftmart_currency_registry::GenesisCurrency<Runtime>> = vec![
+		(2, b"Currency 2".to_vec(), b"CUR2".to_vec(), 12, 0),
+		(3, b"Currency 3".to_vec(), b"CUR3".to_vec(), 12, 0),
+		(4, b"Currency 4".to_vec(), b"CUR4".to_vec(), 12, 0),
+	];
+}
+
+impl nftmart_currency_registry::Config for Runtime {
+	type Event = Event;
+	type CurrencyId = sp_core::constants_types::CurrencyId;
+	type Balance = Balance;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type NativeDecimals = NativeDecimals;
+	type RegistryOrigin = EnsureRootOrHalfCouncil;
+	type StringLimit = CurrencyStringLimit;
+	type InitialCurrencies = InitialCurrencies;
+}
+
+parameter_types! {
+	pub const TokenMinVestedTransfer: Balance = 1 * DOLLARS;
+	pub const MaxVestingSchedules: u32 = 32;
+}
+
+impl nftmart_vesting::Config for Runtime {
+	type Event = Event;
+	type MultiCurrency = Currencies;
+	type MinVestedTransfer = TokenMinVestedTransfer;
+	type MaxVestingSchedules = MaxVestingSchedules;
+}
+
+impl pallet_membership::Config<pallet_membership::Instance2> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrHalfCouncil;
+	type RemoveOrigin = EnsureRootOrHalfCouncil;
+	type SwapOrigin = EnsureRootOrHalfCouncil;
+	type ResetOrigin = EnsureRootOrHalfCouncil;
+	type PrimeOrigin = EnsureRootOrHalfCouncil;
+	type MembershipInitialized = Oracle;
+	type MembershipChanged = Oracle;
+}
+
+parameter_types! {
+	pub const OracleMinimumCount: u32 = 1;
+	pub const OracleExpiresIn: Moment = 1000 * 60 * 60; // 1 hour
+}
+
+impl nftmart_oracle::Config for Runtime {
+	type Event = Event;
+	type OracleKey = sp_core::constants_types::CurrencyId;
+	type OracleValue = nftmart_fee_currency::Price;
+	type OnNewData = ();
+	type Time = Timestamp;
+	type Members = OracleMembership;
+	type MinimumCount = OracleMinimumCount;
+	type ExpiresIn = OracleExpiresIn;
+}
+
+/// Relative prices of two currencies from the oracle.
+pub type OraclePriceProvider = orml_traits::DefaultPriceProvider<sp_core::constants_types::CurrencyId, Oracle>;
+
+impl nftmart_fee_currency::Config for Runtime {
+	type Event = Event;
+	type MultiCurrency = Currencies;
+	type CurrencyRegistry = CurrencyRegistry;
+	type PriceProvider = OraclePriceProvider;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type FeeReceiver = TreasuryAccount;
+}
+
+orml_traits::parameter_type_with_key! {
+	pub DefaultMarketParameters: |parameter: nftmart_config::MarketParameter| -> Balance {
+		match parameter {
+			nftmart_config::MarketParameter::CreateClassDeposit => 2 * CENTS,
+			nftmart_config::MarketParameter::CreateTokenDeposit => 1 * CENTS,
+			nftmart_config::MarketParameter::MetaDataByteDeposit => 10 * MILLICENTS,
+		}
+	};
+}
+
+orml_traits::parameter_type_with_key! {
+	pub MarketParameterBounds: |parameter: nftmart_config::MarketParameter| -> (Balance, Balance) {
+		match parameter {
+			nftmart_config::MarketParameter::CreateClassDeposit => (1 * MILLICENTS, 100 * DOLLARS),
+			nftmart_config::MarketParameter::CreateTokenDeposit => (1 * MILLICENTS, 10 * DOLLARS),
+			nftmart_config::MarketParameter::MetaDataByteDeposit => (1 * MILLICENTS, 1 * DOLLARS),
+		}
+	};
+}
+
+parameter_types! {
+	pub const WhitelistApplicationDeposit: Balance = 10 * DOLLARS;
+	pub const MaxApplicationInfoLen: u32 = 256;
 }
 
 impl nftmart_config::Config for Runtime {
 	type Event = Event;
+	type WhitelistOrigin = EnsureRootOrHalfCouncil;
+	type IdentityJudgements = nftmart_config::Identity<Runtime>;
+	type Currency = Balances;
+	type ApplicationDeposit = WhitelistApplicationDeposit;
+	type MaxApplicationInfoLen = MaxApplicationInfoLen;
+	type ApproveOrigin = EnsureRootOrHalfCouncil;
+	type Slashed = Treasury;
+	type ParameterOrigin = EnsureRootOrHalfCouncil;
+	type DefaultParameters = DefaultMarketParameters;
+	type ParameterBounds = MarketParameterBounds;
+	type PauseOrigin = EnsureOneOf<
+		AccountId,
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, TechnicalCollective>
+	>;
 }
 
 construct_runtime!(
@@ -1191,11 +1336,16 @@ construct_runtime!(
 		Mmr: pallet_mmr::{Module, Storage},
 		Lottery: pallet_lottery::{Module, Call, Storage, Event<T>},
 		Gilt: pallet_gilt::{Module, Call, Storage, Event<T>, Config},
-		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
-		Currencies: orml_currencies::{Module, Call, Event<T>},
+		Tokens: orml_tokens::{Module, Call, Storage, Event<T>, Config<T>},
+		Currencies: orml_currencies::{Module, Call, Storage, Event<T>},
 		OrmlNFT: orml_nft::{Module, Storage, Config<T>},
 		Nftmart: nftmart_nft::{Module, Call, Storage, Event<T>, Config<T>},
 		Config: nftmart_config::{Module, Call, Storage, Event<T>},
+		CurrencyRegistry: nftmart_currency_registry::{Module, Call, Storage, Event<T>, Config<T>},
+		TokenVesting: nftmart_vesting::{Module, Call, Storage, Event<T>},
+		FeeCurrency: nftmart_fee_currency::{Module, Call, Storage, Event<T>},
+		OracleMembership: pallet_membership::<Instance2>::{Module, Call, Storage, Event<T>, Config<T>},
+		Oracle: nftmart_oracle::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -1222,6 +1372,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	nftmart_nft::CheckPaused<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -1253,6 +1404,31 @@ mod mmr {
 	pub type Hashing = <Runtime as pallet_mmr::Config>::Hashing;
 }
 
+/// Convert `amount` of `currency_id` into `quote` with the oracle prices,
+/// along with the time of the oldest price used.
+fn quote_amount(
+	currency_id: sp_core::constants_types::CurrencyId,
+	amount: Balance,
+	quote: sp_core::constants_types::CurrencyId,
+) -> Option<(Balance, Moment)> {
+	use nftmart_currency_registry::CurrencyRegistry as _;
+	use orml_traits::{DataProviderExtended, PriceProvider};
+	use sp_runtime::FixedPointNumber;
+
+	if currency_id == quote {
+		return Some((amount, Timestamp::now()));
+	}
+	let price = OraclePriceProvider::get_price(currency_id, quote)?;
+	let timestamp = Oracle::get_no_op(&currency_id)?.timestamp.min(Oracle::get_no_op(&quote)?.timestamp);
+	// prices are per whole unit, scale the smallest units by the difference in decimals.
+	let amount = nftmart_currency_registry::convert_decimals(
+		price.saturating_mul_int(amount),
+		CurrencyRegistry::decimals(currency_id)?,
+		CurrencyRegistry::decimals(quote)?,
+	)?;
+	Some((amount, timestamp))
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -1447,7 +1623,7 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl nftmart_rpc_runtime_api::NFTMartApi<Block> for Runtime {
+	impl nftmart_rpc_runtime_api::NFTMartApi<Block, AccountId> for Runtime {
 		fn mint_token_deposit(metadata_len: u32, quantity: u32) -> (Balance, Balance) {
 			Nftmart::mint_token_deposit(metadata_len, quantity)
 		}
@@ -1457,6 +1633,56 @@ impl_runtime_apis! {
 		fn create_class_deposit(metadata_len: u32, name_len: u32, description_len: u32) -> (Balance, Balance) {
 			Nftmart::create_class_deposit(metadata_len, name_len, description_len)
 		}
+		fn order_price(
+			class_id: sp_core::constants_types::ClassId,
+			token_id: sp_core::constants_types::TokenId,
+			owner: AccountId,
+			quote: sp_core::constants_types::CurrencyId,
+		) -> Option<(Balance, Moment)> {
+			let (currency_id, price) = Nftmart::order_price(class_id, token_id, &owner)?;
+			quote_amount(currency_id, price, quote)
+		}
+		fn class_floor_price(
+			class_id: sp_core::constants_types::ClassId,
+			quote: sp_core::constants_types::CurrencyId,
+		) -> Option<(Balance, Moment)> {
+			Nftmart::class_order_prices(class_id)
+				.into_iter()
+				.filter_map(|(currency_id, price)| quote_amount(currency_id, price, quote))
+				.min_by_key(|(amount, _)| *amount)
+		}
+		fn remaining_supply(class_id: sp_core::constants_types::ClassId) -> Option<sp_core::constants_types::TokenId> {
+			Nftmart::remaining_supply(class_id)
+		}
+		fn paused_call_groups() -> Vec<nftmart_nft::CallGroup> {
+			nftmart_config::Pallet::<Runtime>::paused_call_groups()
+		}
+	}
+
+	impl orml_tokens_rpc_runtime_api::TokensApi<Block, AccountId> for Runtime {
+		fn account_balances(who: AccountId) -> Vec<(sp_core::constants_types::CurrencyId, orml_tokens_rpc_runtime_api::BalanceInfo<Balance>)> {
+			use orml_traits::{MultiCurrency, MultiReservableCurrency};
+
+			let native_currency_id = GetNativeCurrencyId::get();
+			let native_account = System::account(&who).data;
+			let native = orml_tokens_rpc_runtime_api::BalanceInfo {
+				free: Currencies::free_balance(native_currency_id, &who),
+				reserved: Currencies::reserved_balance(native_currency_id, &who),
+				frozen: native_account.misc_frozen.max(native_account.fee_frozen),
+			};
+			sp_std::iter::once((native_currency_id, native))
+				.chain(Tokens::accounts_of(&who).into_iter().map(|(currency_id, account)| {
+					(currency_id, orml_tokens_rpc_runtime_api::BalanceInfo {
+						free: account.free,
+						reserved: account.reserved,
+						frozen: account.frozen,
+					})
+				}))
+				.collect()
+		}
+		fn total_issuance(currency_id: sp_core::constants_types::CurrencyId) -> Balance {
+			<Currencies as orml_traits::MultiCurrency<AccountId>>::total_issuance(currency_id)
+		}
 	}
 
 	impl pallet_mmr::primitives::MmrApi<
@@ -113,6 +113,7 @@ pub fn config_endowed(
 		pallet_collective_Instance1: Default::default(),
 		pallet_collective_Instance2: Default::default(),
 		pallet_membership_Instance1: Default::default(),
+		pallet_membership_Instance2: Default::default(),
 		pallet_elections_phragmen: Default::default(),
 		pallet_sudo: Default::default(),
 		pallet_treasury: Default::default(),
@@ -126,5 +127,6 @@ pub fn config_endowed(
 		orml_tokens: Default::default(),
 		orml_nft: Default::default(),
 		nftmart_nft: Default::default(),
+		nftmart_currency_registry: Default::default(),
 	}
 }
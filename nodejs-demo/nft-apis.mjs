@@ -114,7 +114,7 @@ async function demo_show_whitelist(ws, keyring) {
 		const len = key.length;
 		key = key.buffer.slice(len - 32, len);
 		const addr = keyring.encodeAddress(new Uint8Array(key));
-		console.log("%s", addr);
+		console.log("%s %s", addr, account[1].toString());
 	}
 }
 
@@ -128,7 +128,8 @@ async function demo_add_whitelist(ws, keyring, sudo, account) {
 		account = account.address;
 	}
 	// const call = api.tx.sudo.sudo(api.tx.config.removeWhitelist(account.address));
-	const call = api.tx.sudo.sudo(api.tx.config.addWhitelist(account));
+	// CreateClass | ReceiveMint | Trade, without expiry.
+	const call = api.tx.sudo.sudo(api.tx.config.addWhitelist(account, 0b111, null));
 	const feeInfo = await call.paymentInfo(sudo.address);
 	console.log("The fee of the call: %s.", feeInfo.partialFee / unit);
 	let [a, b] = waitTx(moduleMetadata);
@@ -172,6 +173,7 @@ async function demo_create_order(ws, keyring, classID, tokenID, account) {
 		classID, tokenID,
 		deposit,
 		currentBlockNumber.add(bnToBn('1000')),
+		[],
 	);
 	const feeInfo = await call.paymentInfo(account);
 	console.log("The fee of the call: %s.", feeInfo.partialFee / unit);
@@ -496,7 +498,7 @@ async function demo_create_class(ws, keyring, account) {
 	// 	/// Token can be burned
 	// 	Burnable = 0b00000010,
 	// }
-	await api.tx.nftmart.createClass("https://xx.com/aa.jpg", "aaa", "bbbb", 1 | 2).signAndSend(account, a);
+	await api.tx.nftmart.createClass("https://xx.com/aa.jpg", "aaa", "bbbb", 1 | 2, null).signAndSend(account, a);
 	await b();
 	process.exit();
 }
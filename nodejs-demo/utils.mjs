@@ -75,6 +75,14 @@ export async function getApi(dest) {
 
 	const types = {
 		Properties: 'u8',
+		Permissions: 'u8',
+		WhitelistInfo: {
+			permissions: 'Permissions',
+			expiry: 'Option<BlockNumber>'
+		},
+		CallGroup: {
+			_enum: ['Trading', 'Minting', 'ClassCreation', 'Transfers']
+		},
 		NFTMetadata: 'Vec<u8>',
 		BlockNumber: 'u32',
 		BlockNumberOf: 'BlockNumber',
@@ -84,7 +92,8 @@ export async function getApi(dest) {
 			price: 'Compact<Balance>',
 			deposit: 'Compact<Balance>',
 			deadline: 'Compact<BlockNumberOf>',
-			categoryId: 'Compact<CategoryIdOf>'
+			categoryId: 'Compact<CategoryIdOf>',
+			takers: 'Vec<AccountId>'
 		},
 
 		CategoryId: 'u32',
@@ -112,7 +121,8 @@ export async function getApi(dest) {
 			properties: 'Properties',
 			name: 'Vec<u8>',
 			description: 'Vec<u8>',
-			createBlock: 'Compact<BlockNumberOf>'
+			createBlock: 'Compact<BlockNumberOf>',
+			maxSupply: 'Option<TokenId>'
 		},
 
 		TokenId: 'u64',
@@ -124,7 +134,10 @@ export async function getApi(dest) {
 		}
 	};
 
-	const api = await ApiPromise.create({provider, types});
+	const signedExtensions = {
+		CheckPaused: {extrinsic: {}, payload: {}}
+	};
+	const api = await ApiPromise.create({provider, types, signedExtensions});
 	const [chain, nodeName, nodeVersion] = await Promise.all([
 		api.rpc.system.chain(),
 		api.rpc.system.name(),
@@ -1,16 +1,196 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
+use enumflags2::BitFlags;
 use frame_support::{
 	pallet_prelude::*,
+	traits::{Currency, Imbalance, OnUnbalanced, ReservableCurrency},
 	transactional
 };
 use frame_system::pallet_prelude::*;
+use orml_traits::GetByKey;
+use pallet_identity::{Judgement, RegistrarIndex};
+use sp_core::constants_types::Balance;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
 
 mod mock;
 mod tests;
 
 pub use module::*;
 
+#[repr(u8)]
+#[derive(Encode, Decode, Clone, Copy, BitFlags, RuntimeDebug, PartialEq, Eq)]
+pub enum WhitelistPermission {
+	/// Account can create classes
+	CreateClass = 0b00000001,
+	/// Account can receive minted tokens
+	ReceiveMint = 0b00000010,
+	/// Account can trade on the marketplace
+	Trade = 0b00000100,
+}
+
+#[derive(Clone, Copy, PartialEq, Default, RuntimeDebug)]
+pub struct Permissions(pub BitFlags<WhitelistPermission>);
+
+impl Permissions {
+	/// All the permissions a whitelist entry can carry.
+	pub fn all() -> Self {
+		Self(BitFlags::all())
+	}
+}
+
+impl Eq for Permissions {}
+impl Encode for Permissions {
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.bits().using_encoded(f)
+	}
+}
+impl Decode for Permissions {
+	fn decode<I: codec::Input>(input: &mut I) -> sp_std::result::Result<Self, codec::Error> {
+		let field = u8::decode(input)?;
+		Ok(Self(
+			<BitFlags<WhitelistPermission>>::from_bits(field as u8).map_err(|_| "invalid value")?,
+		))
+	}
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct WhitelistInfo<BlockNumber> {
+	/// What the account is allowed to do.
+	pub permissions: Permissions,
+	/// The block from which the entry is no longer valid, if any.
+	pub expiry: Option<BlockNumber>,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct WhitelistApplication<Balance> {
+	/// Off-chain information about the applicant, such as a link to a portfolio.
+	pub info: Vec<u8>,
+	/// The permissions applied for.
+	pub permissions: Permissions,
+	/// The deposit reserved from the applicant.
+	pub deposit: Balance,
+}
+
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+/// A marketplace parameter tunable at runtime.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum MarketParameter {
+	/// The minimum balance to create class
+	CreateClassDeposit,
+	/// The minimum balance to create token
+	CreateTokenDeposit,
+	/// The amount of balance that must be deposited per byte of metadata
+	MetaDataByteDeposit,
+}
+
+/// Answers the current value of a marketplace parameter.
+pub trait MarketParameters {
+	/// The current value of `parameter`.
+	fn get(parameter: MarketParameter) -> Balance;
+}
+
+/// A group of marketplace calls which can be paused together.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CallGroup {
+	/// Submitting and taking orders and offers
+	Trading,
+	/// Minting tokens
+	Minting,
+	/// Creating classes
+	ClassCreation,
+	/// Transferring tokens
+	Transfers,
+}
+
+/// Answers whether a group of marketplace calls is paused.
+pub trait PausedCalls {
+	/// Whether the calls of `group` are paused.
+	fn is_paused(group: CallGroup) -> bool;
+}
+
+/// Answers whether an account may do something on the marketplace.
+pub trait Whitelist<AccountId> {
+	/// Whether `who` has been granted `permission`.
+	fn is_whitelisted(who: &AccountId, permission: WhitelistPermission) -> bool;
+
+	/// Whether `who` may trade, anyone unless trading is restricted to the `Trade` permission.
+	fn can_trade(who: &AccountId) -> bool;
+}
+
+/// Judgements given to accounts by identity registrars.
+pub trait IdentityJudgements<AccountId> {
+	/// Whether `who` is judged `KnownGood` or `Reasonable` by `registrar`.
+	fn has_good_judgement(who: &AccountId, registrar: RegistrarIndex) -> bool;
+}
+
+impl<AccountId> IdentityJudgements<AccountId> for () {
+	fn has_good_judgement(_who: &AccountId, _registrar: RegistrarIndex) -> bool {
+		false
+	}
+}
+
+/// `IdentityJudgements` read from `pallet_identity`.
+pub struct Identity<T>(PhantomData<T>);
+
+impl<T: pallet_identity::Config> IdentityJudgements<T::AccountId> for Identity<T> {
+	fn has_good_judgement(who: &T::AccountId, registrar: RegistrarIndex) -> bool {
+		pallet_identity::Module::<T>::identity(who).map_or(false, |registration| {
+			registration.judgements.iter().any(|(index, judgement)| {
+				*index == registrar && matches!(judgement, Judgement::KnownGood | Judgement::Reasonable)
+			})
+		})
+	}
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	V1_0_0,
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+pub mod migrations {
+	use super::*;
+
+	/// A whitelist value as stored either before or after `V2_0_0`.
+	///
+	/// Entries used to be stored as `()`, which encodes to nothing.
+	pub struct OldWhitelistInfo<BlockNumber>(Option<WhitelistInfo<BlockNumber>>);
+
+	impl<BlockNumber: Decode> Decode for OldWhitelistInfo<BlockNumber> {
+		fn decode<I: codec::Input>(input: &mut I) -> sp_std::result::Result<Self, codec::Error> {
+			if input.remaining_len()? == Some(0) {
+				Ok(Self(None))
+			} else {
+				WhitelistInfo::decode(input).map(|info| Self(Some(info)))
+			}
+		}
+	}
+
+	/// Grant all the permissions without expiry to the accounts whitelisted before.
+	pub fn migrate_whitelist<T: Config>() -> Weight {
+		let mut count: Weight = 0;
+		AccountWhitelist::<T>::translate::<OldWhitelistInfo<T::BlockNumber>, _>(|_, old| {
+			count = count.saturating_add(1);
+			Some(old.0.unwrap_or(WhitelistInfo { permissions: Permissions::all(), expiry: None }))
+		});
+		T::DbWeight::get().reads_writes(count, count)
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -18,19 +198,82 @@ pub mod module {
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may manage the whitelist.
+		type WhitelistOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The identity judgements an account may be whitelisted by.
+		type IdentityJudgements: IdentityJudgements<Self::AccountId>;
+
+		/// The currency the application deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved when applying for the whitelist.
+		#[pallet::constant]
+		type ApplicationDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum length of the information attached to an application.
+		#[pallet::constant]
+		type MaxApplicationInfoLen: Get<u32>;
+
+		/// The origin which may approve or reject whitelist applications.
+		type ApproveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Handler for the deposits slashed from rejected applications.
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// The origin which may update the marketplace parameters.
+		type ParameterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The value of a marketplace parameter which has never been updated.
+		type DefaultParameters: GetByKey<MarketParameter, Balance>;
+
+		/// The inclusive range a marketplace parameter can be updated within.
+		type ParameterBounds: GetByKey<MarketParameter, (Balance, Balance)>;
+
+		/// The origin which may pause or unpause marketplace calls.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
+		/// The expiry block had passed.
+		InvalidExpiry,
+		/// The account has a pending application already.
+		ApplicationExists,
+		/// No pending application of the account.
+		ApplicationNotFound,
+		/// The information attached to the application is too long.
+		ApplicationInfoTooLong,
+		/// The value is out of the bounds of the parameter.
+		ParameterOutOfBounds,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// AddWhitelist \[who\]
-		AddWhitelist(T::AccountId),
+		/// AddWhitelist \[who, permissions, expiry\]
+		AddWhitelist(T::AccountId, Permissions, Option<T::BlockNumber>),
 		/// RemoveWhitelist \[who\]
 		RemoveWhitelist(T::AccountId),
+		/// IdentityWhitelistSet \[identity_whitelist\]
+		IdentityWhitelistSet(Option<(RegistrarIndex, Permissions)>),
+		/// AppliedForWhitelist \[who, permissions, deposit\]
+		AppliedForWhitelist(T::AccountId, Permissions, BalanceOf<T>),
+		/// CancelledApplication \[who\]
+		CancelledApplication(T::AccountId),
+		/// ApprovedApplication \[who\]
+		ApprovedApplication(T::AccountId),
+		/// RejectedApplication \[who, slashed\]
+		RejectedApplication(T::AccountId, BalanceOf<T>),
+		/// UpdatedParameter \[parameter, old_value, new_value\]
+		UpdatedParameter(MarketParameter, Balance, Balance),
+		/// Paused \[group\]
+		Paused(CallGroup),
+		/// Unpaused \[group\]
+		Unpaused(CallGroup),
+		/// TradeRestrictionSet \[restricted\]
+		TradeRestrictionSet(bool),
 	}
 
 	#[pallet::pallet]
@@ -52,30 +295,74 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-		fn on_runtime_upgrade() -> Weight { 0 }
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight: Weight = 0;
+			if StorageVersion::<T>::get() == Releases::V1_0_0 {
+				StorageVersion::<T>::put(Releases::V2_0_0);
+				weight = weight.saturating_add(migrations::migrate_whitelist::<T>());
+			}
+			weight
+		}
+
 		fn integrity_test () {}
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
+			<StorageVersion<T>>::put(Releases::V2_0_0);
 		}
 	}
 
-	/// Whitelist for class creation
+	/// Storage version of the pallet.
+	#[pallet::storage]
+	pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
+	/// Whitelist for class creation, minting and trading
 	#[pallet::storage]
 	#[pallet::getter(fn account_whitelist)]
-	pub type AccountWhitelist<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+	pub type AccountWhitelist<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, WhitelistInfo<T::BlockNumber>>;
+
+	/// The registrar whose `KnownGood` or `Reasonable` judgements whitelist an
+	/// account, and the permissions granted by them.
+	#[pallet::storage]
+	#[pallet::getter(fn identity_whitelist)]
+	pub type IdentityWhitelist<T: Config> = StorageValue<_, (RegistrarIndex, Permissions)>;
+
+	/// Whether trading is restricted to the accounts granted `WhitelistPermission::Trade`.
+	#[pallet::storage]
+	#[pallet::getter(fn trade_restricted)]
+	pub type TradeRestricted<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Pending whitelist applications.
+	#[pallet::storage]
+	#[pallet::getter(fn whitelist_applications)]
+	pub type WhitelistApplications<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, WhitelistApplication<BalanceOf<T>>>;
+
+	/// Marketplace parameters updated at runtime.
+	#[pallet::storage]
+	pub type Parameters<T: Config> = StorageMap<_, Twox64Concat, MarketParameter, Balance>;
+
+	/// The paused groups of marketplace calls.
+	#[pallet::storage]
+	pub type PausedCallGroups<T: Config> = StorageMap<_, Twox64Concat, CallGroup, ()>;
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// add an account into whitelist
+		///
+		/// - `permissions`: what the account is allowed to do.
+		/// - `expiry`: the block from which the entry is no longer valid.
 		#[pallet::weight((100_000, DispatchClass::Operational))]
 		#[transactional]
-		pub fn add_whitelist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-			AccountWhitelist::<T>::insert(&who, ());
-			Self::deposit_event(Event::AddWhitelist(who));
+		pub fn add_whitelist(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			permissions: Permissions,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+			Self::do_add_whitelist(who, permissions, expiry)?;
 			Ok((None, Pays::No).into())
 		}
 
@@ -83,10 +370,227 @@ pub mod module {
 		#[pallet::weight((100_000, DispatchClass::Operational))]
 		#[transactional]
 		pub fn remove_whitelist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-			AccountWhitelist::<T>::remove(&who);
-			Self::deposit_event(Event::RemoveWhitelist(who));
+			T::WhitelistOrigin::ensure_origin(origin)?;
+			Self::do_remove_whitelist(who);
+			Ok((None, Pays::No).into())
+		}
+
+		/// add accounts into whitelist, with the same permissions and expiry
+		#[pallet::weight(((accounts.len() as Weight).saturating_mul(100_000), DispatchClass::Operational))]
+		#[transactional]
+		pub fn add_whitelist_batch(
+			origin: OriginFor<T>,
+			accounts: Vec<T::AccountId>,
+			permissions: Permissions,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+			for who in accounts {
+				Self::do_add_whitelist(who, permissions, expiry)?;
+			}
+			Ok((None, Pays::No).into())
+		}
+
+		/// remove accounts from whitelist
+		#[pallet::weight(((accounts.len() as Weight).saturating_mul(100_000), DispatchClass::Operational))]
+		#[transactional]
+		pub fn remove_whitelist_batch(origin: OriginFor<T>, accounts: Vec<T::AccountId>) -> DispatchResultWithPostInfo {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+			for who in accounts {
+				Self::do_remove_whitelist(who);
+			}
+			Ok((None, Pays::No).into())
+		}
+
+		/// whitelist the accounts judged by a registrar, or stop doing so with `None`
+		///
+		/// - `identity_whitelist`: the registrar index and the permissions granted by its judgements.
+		#[pallet::weight((100_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_identity_whitelist(
+			origin: OriginFor<T>,
+			identity_whitelist: Option<(RegistrarIndex, Permissions)>,
+		) -> DispatchResultWithPostInfo {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+			IdentityWhitelist::<T>::set(identity_whitelist);
+			Self::deposit_event(Event::IdentityWhitelistSet(identity_whitelist));
+			Ok((None, Pays::No).into())
+		}
+
+		/// restrict trading to the accounts granted the `Trade` permission, or open it to anyone
+		///
+		/// - `restricted`: whether the `Trade` permission is required.
+		#[pallet::weight((100_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_trade_restricted(origin: OriginFor<T>, restricted: bool) -> DispatchResultWithPostInfo {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+			TradeRestricted::<T>::put(restricted);
+			Self::deposit_event(Event::TradeRestrictionSet(restricted));
+			Ok((None, Pays::No).into())
+		}
+
+		/// apply for the whitelist, reserving `ApplicationDeposit` until the application is decided
+		///
+		/// - `info`: off-chain information about the applicant, with len limitation.
+		/// - `permissions`: the permissions applied for.
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn apply_for_whitelist(origin: OriginFor<T>, info: Vec<u8>, permissions: Permissions) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(info.len() <= T::MaxApplicationInfoLen::get() as usize, Error::<T>::ApplicationInfoTooLong);
+			ensure!(!WhitelistApplications::<T>::contains_key(&who), Error::<T>::ApplicationExists);
+
+			let deposit = T::ApplicationDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			WhitelistApplications::<T>::insert(&who, WhitelistApplication { info, permissions, deposit });
+			Self::deposit_event(Event::AppliedForWhitelist(who, permissions, deposit));
+			Ok(().into())
+		}
+
+		/// withdraw the pending application of the origin, returning its deposit
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn cancel_application(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let application = WhitelistApplications::<T>::take(&who).ok_or(Error::<T>::ApplicationNotFound)?;
+			T::Currency::unreserve(&who, application.deposit);
+			Self::deposit_event(Event::CancelledApplication(who));
+			Ok(().into())
+		}
+
+		/// approve a pending application, returning its deposit and whitelisting the applicant
+		///
+		/// The permissions are added to an unexpired entry of the applicant, which keeps the later
+		/// of both expiries.
+		///
+		/// - `expiry`: the block from which the whitelist entry is no longer valid.
+		#[pallet::weight((100_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn approve_application(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let application = WhitelistApplications::<T>::take(&who).ok_or(Error::<T>::ApplicationNotFound)?;
+			T::Currency::unreserve(&who, application.deposit);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(expiry.map_or(true, |expiry| expiry > now), Error::<T>::InvalidExpiry);
+			let existing = Self::account_whitelist(&who).filter(|info| info.expiry.map_or(true, |expiry| expiry > now));
+			let (permissions, expiry) = match existing {
+				Some(info) => (
+					Permissions(info.permissions.0 | application.permissions.0),
+					info.expiry.and_then(|existing| expiry.map(|expiry| existing.max(expiry))),
+				),
+				None => (application.permissions, expiry),
+			};
+			Self::do_add_whitelist(who.clone(), permissions, expiry)?;
+			Self::deposit_event(Event::ApprovedApplication(who));
+			Ok((None, Pays::No).into())
+		}
+
+		/// reject a pending application, slashing its deposit
+		#[pallet::weight((100_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn reject_application(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let application = WhitelistApplications::<T>::take(&who).ok_or(Error::<T>::ApplicationNotFound)?;
+			let (imbalance, _) = T::Currency::slash_reserved(&who, application.deposit);
+			let slashed = imbalance.peek();
+			T::Slashed::on_unbalanced(imbalance);
+			Self::deposit_event(Event::RejectedApplication(who, slashed));
 			Ok((None, Pays::No).into())
 		}
+
+		/// update a marketplace parameter, within its bounds
+		#[pallet::weight((100_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_parameter(origin: OriginFor<T>, parameter: MarketParameter, value: Balance) -> DispatchResultWithPostInfo {
+			T::ParameterOrigin::ensure_origin(origin)?;
+			let (min, max) = T::ParameterBounds::get(&parameter);
+			ensure!(min <= value && value <= max, Error::<T>::ParameterOutOfBounds);
+
+			let old = <Self as MarketParameters>::get(parameter);
+			Parameters::<T>::insert(parameter, value);
+			Self::deposit_event(Event::UpdatedParameter(parameter, old, value));
+			Ok((None, Pays::No).into())
+		}
+
+		/// pause the calls of `group`, they are rejected until unpaused
+		#[pallet::weight((100_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn pause(origin: OriginFor<T>, group: CallGroup) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			PausedCallGroups::<T>::insert(group, ());
+			Self::deposit_event(Event::Paused(group));
+			Ok((None, Pays::No).into())
+		}
+
+		/// unpause the calls of `group`
+		#[pallet::weight((100_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn unpause(origin: OriginFor<T>, group: CallGroup) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			PausedCallGroups::<T>::remove(group);
+			Self::deposit_event(Event::Unpaused(group));
+			Ok((None, Pays::No).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn do_add_whitelist(who: T::AccountId, permissions: Permissions, expiry: Option<T::BlockNumber>) -> DispatchResult {
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(expiry.map_or(true, |expiry| expiry > now), Error::<T>::InvalidExpiry);
+		AccountWhitelist::<T>::insert(&who, WhitelistInfo { permissions, expiry });
+		Self::deposit_event(Event::AddWhitelist(who, permissions, expiry));
+		Ok(())
+	}
+
+	fn do_remove_whitelist(who: T::AccountId) {
+		AccountWhitelist::<T>::remove(&who);
+		Self::deposit_event(Event::RemoveWhitelist(who));
+	}
+
+	/// All the paused groups of marketplace calls.
+	pub fn paused_call_groups() -> Vec<CallGroup> {
+		PausedCallGroups::<T>::iter().map(|(group, _)| group).collect()
+	}
+
+	/// Whether `who` has an unexpired whitelist entry granting `permission`.
+	fn in_account_whitelist(who: &T::AccountId, permission: WhitelistPermission) -> bool {
+		Self::account_whitelist(who).map_or(false, |info| {
+			info.permissions.0.contains(permission) &&
+				info.expiry.map_or(true, |expiry| frame_system::Pallet::<T>::block_number() < expiry)
+		})
+	}
+
+	/// Whether `who` is judged by the configured registrar, which grants `permission`.
+	fn in_identity_whitelist(who: &T::AccountId, permission: WhitelistPermission) -> bool {
+		Self::identity_whitelist().map_or(false, |(registrar, permissions)| {
+			permissions.0.contains(permission) && T::IdentityJudgements::has_good_judgement(who, registrar)
+		})
+	}
+}
+
+impl<T: Config> MarketParameters for Pallet<T> {
+	fn get(parameter: MarketParameter) -> Balance {
+		Parameters::<T>::get(parameter).unwrap_or_else(|| T::DefaultParameters::get(&parameter))
+	}
+}
+
+impl<T: Config> PausedCalls for Pallet<T> {
+	fn is_paused(group: CallGroup) -> bool {
+		PausedCallGroups::<T>::contains_key(group)
+	}
+}
+
+impl<T: Config> Whitelist<T::AccountId> for Pallet<T> {
+	fn is_whitelisted(who: &T::AccountId, permission: WhitelistPermission) -> bool {
+		Self::in_account_whitelist(who, permission) || Self::in_identity_whitelist(who, permission)
+	}
+
+	fn can_trade(who: &T::AccountId) -> bool {
+		!Self::trade_restricted() || Self::is_whitelisted(who, WhitelistPermission::Trade)
 	}
 }
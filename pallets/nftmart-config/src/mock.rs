@@ -118,8 +118,66 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 }
 
+parameter_types! {
+	pub const BasicDeposit: Balance = 10;
+	pub const FieldDeposit: Balance = 10;
+	pub const SubAccountDeposit: Balance = 10;
+	pub const MaxSubAccounts: u32 = 2;
+	pub const MaxAdditionalFields: u32 = 2;
+	pub const MaxRegistrars: u32 = 20;
+}
+impl pallet_identity::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	type Slashed = ();
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type RegistrarOrigin = frame_system::EnsureRoot<AccountId>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ApplicationDeposit: Balance = 10;
+	pub const MaxApplicationInfoLen: u32 = 64;
+}
+
+orml_traits::parameter_type_with_key! {
+	pub DefaultParameters: |parameter: nftmart_config::MarketParameter| -> Balance {
+		match parameter {
+			nftmart_config::MarketParameter::CreateClassDeposit => 50,
+			nftmart_config::MarketParameter::CreateTokenDeposit => 10,
+			nftmart_config::MarketParameter::MetaDataByteDeposit => 1,
+		}
+	};
+}
+
+orml_traits::parameter_type_with_key! {
+	pub ParameterBounds: |parameter: nftmart_config::MarketParameter| -> (Balance, Balance) {
+		match parameter {
+			nftmart_config::MarketParameter::MetaDataByteDeposit => (1, 10),
+			_ => (1, 100),
+		}
+	};
+}
+
 impl nftmart_config::Config for Runtime {
 	type Event = Event;
+	type WhitelistOrigin = frame_system::EnsureRoot<AccountId>;
+	type IdentityJudgements = nftmart_config::Identity<Runtime>;
+	type Currency = Balances;
+	type ApplicationDeposit = ApplicationDeposit;
+	type MaxApplicationInfoLen = MaxApplicationInfoLen;
+	type ApproveOrigin = frame_system::EnsureRoot<AccountId>;
+	type Slashed = ();
+	type ParameterOrigin = frame_system::EnsureRoot<AccountId>;
+	type PauseOrigin = frame_system::EnsureRoot<AccountId>;
+	type DefaultParameters = DefaultParameters;
+	type ParameterBounds = ParameterBounds;
 }
 
 use frame_system::Call as SystemCall;
@@ -137,12 +195,14 @@ construct_runtime!(
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
 		Utility: pallet_utility::{Module, Call, Event},
+		Identity: pallet_identity::{Module, Call, Storage, Event<T>},
 		NftmartConfig: nftmart_config::{Module, Call, Event<T>},
 	}
 );
 
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
 pub const BOB: AccountId = AccountId::new([2u8; 32]);
+pub const CHARLIE: AccountId = AccountId::new([3u8; 32]);
 
 
 pub struct ExtBuilder;
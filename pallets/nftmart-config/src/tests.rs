@@ -1,19 +1,22 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
-use mock::{Event, *};
+use frame_support::{assert_noop, assert_ok, StorageHasher};
+use mock::{Event, Identity, *};
 
 #[test]
 fn test_whitelist() {
 	ExtBuilder::default().build().execute_with(|| {
+		let permissions = Permissions(WhitelistPermission::CreateClass | WhitelistPermission::ReceiveMint);
 		assert_eq!(None, NftmartConfig::account_whitelist(ALICE));
 		assert_eq!(None, NftmartConfig::account_whitelist(BOB));
-		assert_ok!(NftmartConfig::add_whitelist(Origin::root(), ALICE));
-		assert_eq!(last_event(), Event::nftmart_config(crate::Event::AddWhitelist(ALICE)));
-		assert_eq!(Some(()), NftmartConfig::account_whitelist(ALICE));
+		assert_ok!(NftmartConfig::add_whitelist(Origin::root(), ALICE, permissions, None));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::AddWhitelist(ALICE, permissions, None)));
+		assert_eq!(Some(WhitelistInfo { permissions, expiry: None }), NftmartConfig::account_whitelist(ALICE));
+		assert!(NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::CreateClass));
+		assert!(!NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::Trade));
 		assert_noop!(
-			NftmartConfig::add_whitelist(Origin::signed(BOB), BOB),
+			NftmartConfig::add_whitelist(Origin::signed(BOB), BOB, permissions, None),
 			DispatchError::BadOrigin,
 		);
 
@@ -23,3 +26,266 @@ fn test_whitelist() {
 		assert_eq!(None, NftmartConfig::account_whitelist(BOB));
 	});
 }
+
+#[test]
+fn test_whitelist_expiry() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NftmartConfig::add_whitelist(Origin::root(), ALICE, Permissions::all(), Some(1)),
+			Error::<Runtime>::InvalidExpiry,
+		);
+		assert_ok!(NftmartConfig::add_whitelist(Origin::root(), ALICE, Permissions::all(), Some(3)));
+		assert!(NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::Trade));
+
+		System::set_block_number(3);
+		assert!(!NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::Trade));
+		assert!(NftmartConfig::account_whitelist(ALICE).is_some());
+	});
+}
+
+#[test]
+fn test_whitelist_batch() {
+	ExtBuilder::default().build().execute_with(|| {
+		let permissions = Permissions(WhitelistPermission::ReceiveMint.into());
+		assert_ok!(NftmartConfig::add_whitelist_batch(Origin::root(), vec![ALICE, BOB, CHARLIE], permissions, Some(10)));
+		for who in &[ALICE, BOB, CHARLIE] {
+			assert_eq!(Some(WhitelistInfo { permissions, expiry: Some(10) }), NftmartConfig::account_whitelist(who));
+			assert!(NftmartConfig::is_whitelisted(who, WhitelistPermission::ReceiveMint));
+			assert!(!NftmartConfig::is_whitelisted(who, WhitelistPermission::CreateClass));
+		}
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::AddWhitelist(CHARLIE, permissions, Some(10))));
+		assert_noop!(
+			NftmartConfig::remove_whitelist_batch(Origin::signed(ALICE), vec![ALICE]),
+			DispatchError::BadOrigin,
+		);
+
+		assert_ok!(NftmartConfig::remove_whitelist_batch(Origin::root(), vec![ALICE, CHARLIE]));
+		assert_eq!(None, NftmartConfig::account_whitelist(ALICE));
+		assert_eq!(None, NftmartConfig::account_whitelist(CHARLIE));
+		assert!(NftmartConfig::account_whitelist(BOB).is_some());
+	});
+}
+
+#[test]
+fn migrate_whitelist_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		frame_support::storage::migration::put_storage_value(
+			b"NftmartConfig", b"AccountWhitelist", &Blake2_128Concat::hash(&ALICE.encode()), ());
+		assert_ok!(NftmartConfig::add_whitelist(Origin::root(), BOB, Permissions(WhitelistPermission::Trade.into()), None));
+
+		migrations::migrate_whitelist::<Runtime>();
+		assert_eq!(
+			Some(WhitelistInfo { permissions: Permissions::all(), expiry: None }),
+			NftmartConfig::account_whitelist(ALICE),
+		);
+		assert_eq!(
+			Some(WhitelistInfo { permissions: Permissions(WhitelistPermission::Trade.into()), expiry: None }),
+			NftmartConfig::account_whitelist(BOB),
+		);
+	});
+}
+
+#[test]
+fn test_identity_whitelist() {
+	ExtBuilder::default().build().execute_with(|| {
+		let permissions = Permissions(WhitelistPermission::CreateClass | WhitelistPermission::ReceiveMint);
+		assert_ok!(Identity::add_registrar(Origin::root(), BOB));
+		assert_ok!(Identity::add_registrar(Origin::root(), CHARLIE));
+		assert_ok!(Identity::set_identity(Origin::signed(ALICE), pallet_identity::IdentityInfo::default()));
+		assert_ok!(Identity::provide_judgement(Origin::signed(CHARLIE), 1, ALICE, pallet_identity::Judgement::KnownGood));
+		assert!(!NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::CreateClass));
+
+		assert_noop!(
+			NftmartConfig::set_identity_whitelist(Origin::signed(ALICE), Some((0, permissions))),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(NftmartConfig::set_identity_whitelist(Origin::root(), Some((0, permissions))));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::IdentityWhitelistSet(Some((0, permissions)))));
+		assert!(!NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::CreateClass));
+
+		assert_ok!(Identity::provide_judgement(Origin::signed(BOB), 0, ALICE, pallet_identity::Judgement::LowQuality));
+		assert!(!NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::CreateClass));
+		assert_ok!(Identity::provide_judgement(Origin::signed(BOB), 0, ALICE, pallet_identity::Judgement::Reasonable));
+		assert!(NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::CreateClass));
+		assert!(!NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::Trade));
+		assert_eq!(None, NftmartConfig::account_whitelist(ALICE));
+
+		// explicit entries still count
+		assert_ok!(NftmartConfig::add_whitelist(Origin::root(), ALICE, Permissions(WhitelistPermission::Trade.into()), None));
+		assert!(NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::Trade));
+
+		assert_ok!(NftmartConfig::set_identity_whitelist(Origin::root(), None));
+		assert!(!NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::CreateClass));
+		assert!(NftmartConfig::is_whitelisted(&ALICE, WhitelistPermission::Trade));
+	});
+}
+
+#[test]
+fn test_whitelist_application() {
+	ExtBuilder::default().build().execute_with(|| {
+		let permissions = Permissions(WhitelistPermission::CreateClass.into());
+		assert_noop!(
+			NftmartConfig::apply_for_whitelist(Origin::signed(ALICE), vec![1; 65], permissions),
+			Error::<Runtime>::ApplicationInfoTooLong,
+		);
+		assert_noop!(
+			NftmartConfig::apply_for_whitelist(Origin::signed(BOB), vec![1], permissions),
+			pallet_balances::Error::<Runtime, _>::InsufficientBalance,
+		);
+		assert_ok!(NftmartConfig::apply_for_whitelist(Origin::signed(ALICE), vec![1], permissions));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::AppliedForWhitelist(ALICE, permissions, 10)));
+		assert_eq!(Balances::reserved_balance(&ALICE), 10);
+		assert_eq!(
+			Some(WhitelistApplication { info: vec![1], permissions, deposit: 10 }),
+			NftmartConfig::whitelist_applications(ALICE),
+		);
+		assert_noop!(
+			NftmartConfig::apply_for_whitelist(Origin::signed(ALICE), vec![2], permissions),
+			Error::<Runtime>::ApplicationExists,
+		);
+
+		assert_noop!(
+			NftmartConfig::approve_application(Origin::signed(BOB), ALICE, None),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			NftmartConfig::approve_application(Origin::root(), BOB, None),
+			Error::<Runtime>::ApplicationNotFound,
+		);
+		assert_ok!(NftmartConfig::approve_application(Origin::root(), ALICE, Some(10)));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::ApprovedApplication(ALICE)));
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		assert_eq!(None, NftmartConfig::whitelist_applications(ALICE));
+		assert_eq!(Some(WhitelistInfo { permissions, expiry: Some(10) }), NftmartConfig::account_whitelist(ALICE));
+	});
+}
+
+#[test]
+fn test_whitelist_application_merges_entry() {
+	ExtBuilder::default().build().execute_with(|| {
+		let receive_mint = Permissions(WhitelistPermission::ReceiveMint.into());
+		let create_class = Permissions(WhitelistPermission::CreateClass.into());
+		let both = Permissions(WhitelistPermission::ReceiveMint | WhitelistPermission::CreateClass);
+
+		assert_ok!(NftmartConfig::add_whitelist(Origin::root(), ALICE, receive_mint, None));
+		assert_ok!(NftmartConfig::apply_for_whitelist(Origin::signed(ALICE), vec![1], create_class));
+		assert_ok!(NftmartConfig::approve_application(Origin::root(), ALICE, Some(10)));
+		assert_eq!(Some(WhitelistInfo { permissions: both, expiry: None }), NftmartConfig::account_whitelist(ALICE));
+
+		// an expired entry is replaced.
+		assert_ok!(NftmartConfig::add_whitelist(Origin::root(), ALICE, receive_mint, Some(5)));
+		System::set_block_number(5);
+		assert_ok!(NftmartConfig::apply_for_whitelist(Origin::signed(ALICE), vec![1], create_class));
+		assert_ok!(NftmartConfig::approve_application(Origin::root(), ALICE, Some(10)));
+		assert_eq!(
+			Some(WhitelistInfo { permissions: create_class, expiry: Some(10) }),
+			NftmartConfig::account_whitelist(ALICE)
+		);
+
+		// an unexpired entry keeps the later expiry.
+		assert_ok!(NftmartConfig::apply_for_whitelist(Origin::signed(ALICE), vec![1], receive_mint));
+		assert_ok!(NftmartConfig::approve_application(Origin::root(), ALICE, Some(20)));
+		assert_eq!(Some(WhitelistInfo { permissions: both, expiry: Some(20) }), NftmartConfig::account_whitelist(ALICE));
+	});
+}
+
+#[test]
+fn test_whitelist_application_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		let permissions = Permissions::all();
+		assert_ok!(NftmartConfig::apply_for_whitelist(Origin::signed(ALICE), vec![1], permissions));
+		assert_ok!(NftmartConfig::cancel_application(Origin::signed(ALICE)));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::CancelledApplication(ALICE)));
+		assert_eq!(Balances::free_balance(&ALICE), 100);
+		assert_noop!(
+			NftmartConfig::cancel_application(Origin::signed(ALICE)),
+			Error::<Runtime>::ApplicationNotFound,
+		);
+
+		assert_ok!(NftmartConfig::apply_for_whitelist(Origin::signed(ALICE), vec![1], permissions));
+		assert_noop!(
+			NftmartConfig::reject_application(Origin::signed(ALICE), ALICE),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(NftmartConfig::reject_application(Origin::root(), ALICE));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::RejectedApplication(ALICE, 10)));
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		assert_eq!(Balances::free_balance(&ALICE), 90);
+		assert_eq!(Balances::total_issuance(), 90);
+		assert_eq!(None, NftmartConfig::whitelist_applications(ALICE));
+		assert_eq!(None, NftmartConfig::account_whitelist(ALICE));
+	});
+}
+
+#[test]
+fn test_set_parameter() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(<NftmartConfig as MarketParameters>::get(MarketParameter::CreateClassDeposit), 50);
+		assert_noop!(
+			NftmartConfig::set_parameter(Origin::signed(ALICE), MarketParameter::CreateClassDeposit, 20),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			NftmartConfig::set_parameter(Origin::root(), MarketParameter::CreateClassDeposit, 0),
+			Error::<Runtime>::ParameterOutOfBounds,
+		);
+		assert_noop!(
+			NftmartConfig::set_parameter(Origin::root(), MarketParameter::MetaDataByteDeposit, 11),
+			Error::<Runtime>::ParameterOutOfBounds,
+		);
+
+		assert_ok!(NftmartConfig::set_parameter(Origin::root(), MarketParameter::CreateClassDeposit, 20));
+		assert_eq!(
+			last_event(),
+			Event::nftmart_config(crate::Event::UpdatedParameter(MarketParameter::CreateClassDeposit, 50, 20)),
+		);
+		assert_eq!(<NftmartConfig as MarketParameters>::get(MarketParameter::CreateClassDeposit), 20);
+		assert_eq!(<NftmartConfig as MarketParameters>::get(MarketParameter::CreateTokenDeposit), 10);
+	});
+}
+
+#[test]
+fn test_pause() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(!<NftmartConfig as PausedCalls>::is_paused(CallGroup::Trading));
+		assert_noop!(
+			NftmartConfig::pause(Origin::signed(ALICE), CallGroup::Trading),
+			DispatchError::BadOrigin,
+		);
+
+		assert_ok!(NftmartConfig::pause(Origin::root(), CallGroup::Trading));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::Paused(CallGroup::Trading)));
+		assert!(<NftmartConfig as PausedCalls>::is_paused(CallGroup::Trading));
+		assert!(!<NftmartConfig as PausedCalls>::is_paused(CallGroup::Minting));
+		assert_eq!(NftmartConfig::paused_call_groups(), vec![CallGroup::Trading]);
+
+		assert_noop!(
+			NftmartConfig::unpause(Origin::signed(ALICE), CallGroup::Trading),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(NftmartConfig::unpause(Origin::root(), CallGroup::Trading));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::Unpaused(CallGroup::Trading)));
+		assert!(!<NftmartConfig as PausedCalls>::is_paused(CallGroup::Trading));
+		assert!(NftmartConfig::paused_call_groups().is_empty());
+	});
+}
+
+#[test]
+fn test_trade_restriction() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert!(NftmartConfig::can_trade(&ALICE));
+		assert_noop!(
+			NftmartConfig::set_trade_restricted(Origin::signed(ALICE), true),
+			DispatchError::BadOrigin,
+		);
+
+		assert_ok!(NftmartConfig::set_trade_restricted(Origin::root(), true));
+		assert_eq!(last_event(), Event::nftmart_config(crate::Event::TradeRestrictionSet(true)));
+		assert!(!NftmartConfig::can_trade(&ALICE));
+		assert_ok!(NftmartConfig::add_whitelist(Origin::root(), ALICE, Permissions(WhitelistPermission::Trade.into()), None));
+		assert!(NftmartConfig::can_trade(&ALICE));
+
+		assert_ok!(NftmartConfig::set_trade_restricted(Origin::root(), false));
+		assert!(NftmartConfig::can_trade(&BOB));
+	});
+}
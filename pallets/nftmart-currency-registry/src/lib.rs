@@ -0,0 +1,334 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::Filter,
+	transactional
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::GetByKey;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+	traits::{checked_pow, AtLeast32BitUnsigned, Zero},
+	DispatchResult, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+
+pub use module::*;
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CurrencyMetadata<Balance> {
+	/// Name of the currency.
+	pub name: Vec<u8>,
+	/// Ticker symbol of the currency.
+	pub symbol: Vec<u8>,
+	/// Number of decimals of the currency.
+	pub decimals: u8,
+	/// The minimum balance an account must hold.
+	pub existential_deposit: Balance,
+	/// A deprecated currency can no longer be used for new orders.
+	pub deprecated: bool,
+}
+
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	V1_0_0,
+}
+
+/// Information about the currencies usable in the marketplace.
+pub trait CurrencyRegistry<CurrencyId> {
+	/// Ensure `currency_id` is registered and not deprecated.
+	fn ensure_active(currency_id: CurrencyId) -> DispatchResult;
+
+	/// Number of decimals of `currency_id`, `None` if it is not registered.
+	fn decimals(currency_id: CurrencyId) -> Option<u8>;
+}
+
+/// Every currency is active and has the same number of decimals.
+impl<CurrencyId> CurrencyRegistry<CurrencyId> for () {
+	fn ensure_active(_: CurrencyId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn decimals(_: CurrencyId) -> Option<u8> {
+		Some(0)
+	}
+}
+
+/// Convert `amount` from a currency with `from` decimals into the smallest unit of one with `to` decimals,
+/// `None` on overflow.
+pub fn convert_decimals<Balance: AtLeast32BitUnsigned + Copy>(amount: Balance, from: u8, to: u8) -> Option<Balance> {
+	if to >= from {
+		checked_pow(Balance::from(10u32), (to - from) as usize).and_then(|scale| amount.checked_mul(&scale))
+	} else {
+		Some(checked_pow(Balance::from(10u32), (from - to) as usize).map_or_else(Zero::zero, |scale| amount / scale))
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub type GenesisCurrency<T> = (
+		<T as Config>::CurrencyId,
+		Vec<u8>, // name
+		Vec<u8>, // symbol
+		u8, // decimals
+		<T as Config>::Balance, // existential deposit
+	);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency ID type
+		type CurrencyId: Parameter + Member + Copy + MaybeSerializeDeserialize + Ord;
+
+		/// The balance type
+		type Balance: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaybeSerializeDeserialize;
+
+		/// The native currency, always active and never registered.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<Self::CurrencyId>;
+
+		/// Number of decimals of the native currency.
+		#[pallet::constant]
+		type NativeDecimals: Get<u8>;
+
+		/// The origin which may register, update or deprecate currencies.
+		type RegistryOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum length of a name or symbol.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// The currencies in use before the registry, registered by the runtime upgrade adding it.
+		type InitialCurrencies: Get<Vec<GenesisCurrency<Self>>>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Currency not registered
+		CurrencyNotRegistered,
+		/// Currency already registered
+		CurrencyAlreadyRegistered,
+		/// Currency deprecated
+		CurrencyDeprecated,
+		/// The native currency can not be registered
+		NativeCurrency,
+		/// Name or symbol too long
+		BadMetadata,
+		/// The existential deposit of a registered currency can not be increased
+		ExistentialDepositIncreased,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Registered a currency. \[currency_id\]
+		RegisteredCurrency(T::CurrencyId),
+		/// Updated a currency. \[currency_id\]
+		UpdatedCurrency(T::CurrencyId),
+		/// Deprecated a currency. \[currency_id\]
+		DeprecatedCurrency(T::CurrencyId),
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub currencies: Vec<GenesisCurrency<T>>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				currencies: vec![],
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::<T>::get().is_some() {
+				return T::DbWeight::get().reads(1);
+			}
+			StorageVersion::<T>::put(Releases::V1_0_0);
+			let currencies = T::InitialCurrencies::get();
+			let count = currencies.len() as Weight;
+			for (currency_id, name, symbol, decimals, existential_deposit) in currencies {
+				if Pallet::<T>::do_register(currency_id, name, symbol, decimals, existential_deposit).is_ok() {
+					Pallet::<T>::deposit_event(Event::RegisteredCurrency(currency_id));
+				}
+			}
+			T::DbWeight::get().reads_writes(count.saturating_add(1), count.saturating_add(1))
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			self.currencies.iter().for_each(|(currency_id, name, symbol, decimals, existential_deposit)| {
+				Pallet::<T>::do_register(*currency_id, name.clone(), symbol.clone(), *decimals, *existential_deposit)
+					.expect("Register currency cannot fail while building genesis");
+			});
+			StorageVersion::<T>::put(Releases::V1_0_0);
+		}
+	}
+
+	/// Storage version of the pallet, `None` until the initial currencies are registered.
+	#[pallet::storage]
+	pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases>;
+
+	/// Metadata of registered currencies.
+	#[pallet::storage]
+	#[pallet::getter(fn currencies)]
+	pub type Currencies<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, CurrencyMetadata<T::Balance>>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a new currency.
+		///
+		/// - `currency_id`: currency id
+		/// - `name`: currency name, with len limitation.
+		/// - `symbol`: ticker symbol, with len limitation.
+		/// - `decimals`: number of decimals
+		/// - `existential_deposit`: the minimum balance an account must hold
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn register_currency(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			existential_deposit: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			T::RegistryOrigin::ensure_origin(origin)?;
+			Self::do_register(currency_id, name, symbol, decimals, existential_deposit)?;
+			Self::deposit_event(Event::RegisteredCurrency(currency_id));
+			Ok(().into())
+		}
+
+		/// Update the metadata of a registered currency.
+		///
+		/// - `currency_id`: currency id
+		/// - `name`: currency name, with len limitation.
+		/// - `symbol`: ticker symbol, with len limitation.
+		/// - `decimals`: number of decimals
+		/// - `existential_deposit`: the minimum balance an account must hold, which can only be
+		///   lowered as existing balances would become dust otherwise.
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn update_currency(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8,
+			existential_deposit: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			T::RegistryOrigin::ensure_origin(origin)?;
+			Self::ensure_metadata(&name, &symbol)?;
+			Currencies::<T>::try_mutate(currency_id, |maybe_currency| -> DispatchResult {
+				let currency = maybe_currency.as_mut().ok_or(Error::<T>::CurrencyNotRegistered)?;
+				ensure!(
+					existential_deposit <= currency.existential_deposit,
+					Error::<T>::ExistentialDepositIncreased
+				);
+				currency.name = name;
+				currency.symbol = symbol;
+				currency.decimals = decimals;
+				currency.existential_deposit = existential_deposit;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::UpdatedCurrency(currency_id));
+			Ok(().into())
+		}
+
+		/// Deprecate a currency, it can no longer be used for new orders.
+		///
+		/// - `currency_id`: currency id
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn deprecate_currency(origin: OriginFor<T>, currency_id: T::CurrencyId) -> DispatchResultWithPostInfo {
+			T::RegistryOrigin::ensure_origin(origin)?;
+			Currencies::<T>::try_mutate(currency_id, |maybe_currency| -> DispatchResult {
+				let currency = maybe_currency.as_mut().ok_or(Error::<T>::CurrencyNotRegistered)?;
+				ensure!(!currency.deprecated, Error::<T>::CurrencyDeprecated);
+				currency.deprecated = true;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::DeprecatedCurrency(currency_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn ensure_metadata(name: &[u8], symbol: &[u8]) -> DispatchResult {
+		let limit = T::StringLimit::get() as usize;
+		ensure!(name.len() <= limit && symbol.len() <= limit, Error::<T>::BadMetadata);
+		Ok(())
+	}
+
+	fn do_register(
+		currency_id: T::CurrencyId,
+		name: Vec<u8>,
+		symbol: Vec<u8>,
+		decimals: u8,
+		existential_deposit: T::Balance,
+	) -> DispatchResult {
+		ensure!(currency_id != T::GetNativeCurrencyId::get(), Error::<T>::NativeCurrency);
+		ensure!(!Currencies::<T>::contains_key(currency_id), Error::<T>::CurrencyAlreadyRegistered);
+		Self::ensure_metadata(&name, &symbol)?;
+		Currencies::<T>::insert(currency_id, CurrencyMetadata {
+			name,
+			symbol,
+			decimals,
+			existential_deposit,
+			deprecated: false,
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config> CurrencyRegistry<T::CurrencyId> for Pallet<T> {
+	fn ensure_active(currency_id: T::CurrencyId) -> DispatchResult {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			return Ok(());
+		}
+		let currency = Self::currencies(currency_id).ok_or(Error::<T>::CurrencyNotRegistered)?;
+		ensure!(!currency.deprecated, Error::<T>::CurrencyDeprecated);
+		Ok(())
+	}
+
+	fn decimals(currency_id: T::CurrencyId) -> Option<u8> {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			return Some(T::NativeDecimals::get());
+		}
+		Self::currencies(currency_id).map(|c| c.decimals)
+	}
+}
+
+/// Existential deposits of registered currencies, zero for the others.
+impl<T: Config> GetByKey<T::CurrencyId, T::Balance> for Pallet<T> {
+	fn get(currency_id: &T::CurrencyId) -> T::Balance {
+		Self::currencies(currency_id).map_or_else(Zero::zero, |c| c.existential_deposit)
+	}
+}
+
+/// Only registered and active currencies pass the filter.
+impl<T: Config> Filter<T::CurrencyId> for Pallet<T> {
+	fn filter(currency_id: &T::CurrencyId) -> bool {
+		*currency_id != T::GetNativeCurrencyId::get() && Self::ensure_active(*currency_id).is_ok()
+	}
+}
@@ -0,0 +1,108 @@
+#![cfg(test)]
+
+use crate as nftmart_currency_registry;
+use frame_support::{construct_runtime, parameter_types};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u64;
+pub type Balance = u128;
+pub type CurrencyId = u32;
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = 0;
+	pub const NativeDecimals: u8 = 12;
+	pub const StringLimit: u32 = 8;
+	pub InitialCurrencies: Vec<nftmart_currency_registry::GenesisCurrency<Runtime>> = vec![
+		(INITIAL_CURRENCY_ID, b"Initial".to_vec(), b"INIT".to_vec(), 12, 0),
+	];
+}
+
+impl nftmart_currency_registry::Config for Runtime {
+	type Event = Event;
+	type CurrencyId = CurrencyId;
+	type Balance = Balance;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type NativeDecimals = NativeDecimals;
+	type RegistryOrigin = frame_system::EnsureRoot<AccountId>;
+	type StringLimit = StringLimit;
+	type InitialCurrencies = InitialCurrencies;
+}
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, u32, ()>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		CurrencyRegistry: nftmart_currency_registry::{Module, Call, Storage, Event<T>, Config<T>},
+	}
+);
+
+pub const ALICE: AccountId = 1;
+pub const NATIVE_CURRENCY_ID: CurrencyId = 0;
+pub const CURRENCY_ID: CurrencyId = 1;
+pub const CURRENCY_ID_NOT_EXIST: CurrencyId = 100;
+pub const INITIAL_CURRENCY_ID: CurrencyId = 2;
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
+
+pub fn last_event() -> Event {
+	frame_system::Module::<Runtime>::events()
+		.pop()
+		.expect("Event expected")
+		.event
+}
@@ -0,0 +1,116 @@
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{CurrencyRegistry, Event, *};
+use sp_runtime::DispatchError;
+use crate::CurrencyRegistry as _;
+
+#[test]
+fn register_currency_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(CurrencyRegistry::ensure_active(CURRENCY_ID), Error::<Runtime>::CurrencyNotRegistered);
+		assert_ok!(CurrencyRegistry::ensure_active(NATIVE_CURRENCY_ID));
+
+		assert_ok!(CurrencyRegistry::register_currency(Origin::root(), CURRENCY_ID, b"Token".to_vec(), b"TKN".to_vec(), 12, 10));
+		assert_eq!(last_event(), Event::nftmart_currency_registry(crate::Event::RegisteredCurrency(CURRENCY_ID)));
+		assert_eq!(CurrencyRegistry::currencies(CURRENCY_ID), Some(CurrencyMetadata {
+			name: b"Token".to_vec(),
+			symbol: b"TKN".to_vec(),
+			decimals: 12,
+			existential_deposit: 10,
+			deprecated: false,
+		}));
+		assert_ok!(CurrencyRegistry::ensure_active(CURRENCY_ID));
+		assert!(<CurrencyRegistry as Filter<_>>::filter(&CURRENCY_ID));
+		assert!(!<CurrencyRegistry as Filter<_>>::filter(&NATIVE_CURRENCY_ID));
+		assert_eq!(<CurrencyRegistry as GetByKey<_, _>>::get(&CURRENCY_ID), 10);
+		assert_eq!(<CurrencyRegistry as GetByKey<_, _>>::get(&CURRENCY_ID_NOT_EXIST), 0);
+		assert_eq!(CurrencyRegistry::decimals(CURRENCY_ID), Some(12));
+		assert_eq!(CurrencyRegistry::decimals(NATIVE_CURRENCY_ID), Some(12));
+		assert_eq!(CurrencyRegistry::decimals(CURRENCY_ID_NOT_EXIST), None);
+
+		assert_noop!(
+			CurrencyRegistry::update_currency(Origin::root(), CURRENCY_ID, b"Token".to_vec(), b"TKN".to_vec(), 10, 20),
+			Error::<Runtime>::ExistentialDepositIncreased
+		);
+		assert_ok!(CurrencyRegistry::update_currency(Origin::root(), CURRENCY_ID, b"Token".to_vec(), b"TKN".to_vec(), 10, 5));
+		assert_eq!(last_event(), Event::nftmart_currency_registry(crate::Event::UpdatedCurrency(CURRENCY_ID)));
+		assert_eq!(<CurrencyRegistry as GetByKey<_, _>>::get(&CURRENCY_ID), 5);
+
+		assert_ok!(CurrencyRegistry::deprecate_currency(Origin::root(), CURRENCY_ID));
+		assert_eq!(last_event(), Event::nftmart_currency_registry(crate::Event::DeprecatedCurrency(CURRENCY_ID)));
+		assert_noop!(CurrencyRegistry::ensure_active(CURRENCY_ID), Error::<Runtime>::CurrencyDeprecated);
+		assert!(!<CurrencyRegistry as Filter<_>>::filter(&CURRENCY_ID));
+	});
+}
+
+#[test]
+fn convert_decimals_should_work() {
+	assert_eq!(convert_decimals(5u128, 6, 12), Some(5_000_000));
+	assert_eq!(convert_decimals(5_000_000u128, 12, 6), Some(5));
+	assert_eq!(convert_decimals(5_999_999u128, 12, 6), Some(5));
+	assert_eq!(convert_decimals(5u128, 12, 12), Some(5));
+	assert_eq!(convert_decimals(u128::MAX, 0, 1), None);
+	assert_eq!(convert_decimals(u128::MAX, 255, 0), Some(0));
+}
+
+#[test]
+fn register_currency_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CurrencyRegistry::register_currency(Origin::signed(ALICE), CURRENCY_ID, vec![], vec![], 12, 10),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			CurrencyRegistry::register_currency(Origin::root(), NATIVE_CURRENCY_ID, vec![], vec![], 12, 10),
+			Error::<Runtime>::NativeCurrency,
+		);
+		assert_noop!(
+			CurrencyRegistry::register_currency(Origin::root(), CURRENCY_ID, vec![1; 9], vec![], 12, 10),
+			Error::<Runtime>::BadMetadata,
+		);
+		assert_noop!(
+			CurrencyRegistry::update_currency(Origin::root(), CURRENCY_ID, vec![], vec![], 12, 10),
+			Error::<Runtime>::CurrencyNotRegistered,
+		);
+		assert_noop!(
+			CurrencyRegistry::deprecate_currency(Origin::root(), CURRENCY_ID),
+			Error::<Runtime>::CurrencyNotRegistered,
+		);
+
+		assert_ok!(CurrencyRegistry::register_currency(Origin::root(), CURRENCY_ID, vec![], vec![], 12, 10));
+		assert_noop!(
+			CurrencyRegistry::register_currency(Origin::root(), CURRENCY_ID, vec![], vec![], 12, 10),
+			Error::<Runtime>::CurrencyAlreadyRegistered,
+		);
+		assert_ok!(CurrencyRegistry::deprecate_currency(Origin::root(), CURRENCY_ID));
+		assert_noop!(
+			CurrencyRegistry::deprecate_currency(Origin::root(), CURRENCY_ID),
+			Error::<Runtime>::CurrencyDeprecated,
+		);
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_should_register_initial_currencies() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(CurrencyRegistry::ensure_active(INITIAL_CURRENCY_ID), Error::<Runtime>::CurrencyNotRegistered);
+
+		CurrencyRegistry::on_runtime_upgrade();
+		assert_eq!(last_event(), Event::nftmart_currency_registry(crate::Event::RegisteredCurrency(INITIAL_CURRENCY_ID)));
+		assert_eq!(CurrencyRegistry::currencies(INITIAL_CURRENCY_ID), Some(CurrencyMetadata {
+			name: b"Initial".to_vec(),
+			symbol: b"INIT".to_vec(),
+			decimals: 12,
+			existential_deposit: 0,
+			deprecated: false,
+		}));
+		assert_ok!(CurrencyRegistry::ensure_active(INITIAL_CURRENCY_ID));
+
+		// runs only once.
+		assert_ok!(CurrencyRegistry::deprecate_currency(Origin::root(), INITIAL_CURRENCY_ID));
+		CurrencyRegistry::on_runtime_upgrade();
+		assert_noop!(CurrencyRegistry::ensure_active(INITIAL_CURRENCY_ID), Error::<Runtime>::CurrencyDeprecated);
+	});
+}
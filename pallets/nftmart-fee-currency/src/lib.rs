@@ -0,0 +1,211 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::IsSubType,
+	transactional
+};
+use frame_system::pallet_prelude::*;
+use nftmart_currency_registry::{convert_decimals, CurrencyRegistry};
+use orml_traits::{MultiCurrency, PriceProvider};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_core::constants_types::Balance;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+	FixedPointNumber, FixedU128, RuntimeDebug,
+};
+use sp_std::marker::PhantomData;
+
+mod mock;
+mod tests;
+
+pub use module::*;
+
+/// The relative price of two currencies.
+pub type Price = FixedU128;
+
+/// What was withdrawn to pay the fee of a transaction.
+#[derive(RuntimeDebug)]
+pub enum FeeLiquidity<NativeInfo, CurrencyId> {
+	/// Paid by the native adapter.
+	Native(NativeInfo),
+	/// Paid `amount` of `currency_id`, converted from the native fee at `price`.
+	Token {
+		currency_id: CurrencyId,
+		amount: Balance,
+		price: Price,
+	},
+}
+
+impl<NativeInfo: Default, CurrencyId> Default for FeeLiquidity<NativeInfo, CurrencyId> {
+	fn default() -> Self {
+		Self::Native(Default::default())
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub type CurrencyIdOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// MultiCurrency type the fees are withdrawn from
+		type MultiCurrency: MultiCurrency<Self::AccountId, Balance = Balance>;
+
+		/// Registry of the currencies accepted for fees.
+		type CurrencyRegistry: CurrencyRegistry<CurrencyIdOf<Self>>;
+
+		/// Price of the native currency quoted in other currencies.
+		type PriceProvider: PriceProvider<CurrencyIdOf<Self>, Price>;
+
+		/// The native currency id, paid through the native adapter.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
+
+		/// The account receiving the fees paid in non-native currencies.
+		type FeeReceiver: Get<Self::AccountId>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The currency has no price.
+		PriceNotFound,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Set the fee currency of an account, `None` for the native currency. \[who, currency_id\]
+		FeeCurrencySet(T::AccountId, Option<CurrencyIdOf<T>>),
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	/// The currency an account pays transaction fees with, the native currency if absent.
+	#[pallet::storage]
+	#[pallet::getter(fn fee_currencies)]
+	pub type FeeCurrencies<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, CurrencyIdOf<T>>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Choose the currency paying the transaction fees of the sender.
+		///
+		/// The fee of this call is paid with the chosen currency, so that accounts without native
+		/// balance can choose it.
+		///
+		/// - `currency_id`: a registered currency id, `None` for the native currency.
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn set_fee_currency(origin: OriginFor<T>, currency_id: Option<CurrencyIdOf<T>>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let currency_id = currency_id.filter(|id| *id != T::GetNativeCurrencyId::get());
+			match currency_id {
+				Some(currency_id) => {
+					T::CurrencyRegistry::ensure_active(currency_id)?;
+					Self::price_of(currency_id).ok_or(Error::<T>::PriceNotFound)?;
+					FeeCurrencies::<T>::insert(&who, currency_id);
+				},
+				None => FeeCurrencies::<T>::remove(&who),
+			}
+			Self::deposit_event(Event::FeeCurrencySet(who, currency_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The price of one native unit in `currency_id`.
+	pub fn price_of(currency_id: CurrencyIdOf<T>) -> Option<Price> {
+		T::PriceProvider::get_price(T::GetNativeCurrencyId::get(), currency_id)
+			.filter(|price| !price.is_zero())
+	}
+
+	/// Convert a native `fee` into the smallest unit of `currency_id` at `price`, which is per whole unit.
+	///
+	/// Rounds up, so that no fee is waived by a currency with fewer decimals.
+	fn convert_fee(currency_id: CurrencyIdOf<T>, price: Price, fee: Balance) -> Option<Balance> {
+		let native_decimals = T::CurrencyRegistry::decimals(T::GetNativeCurrencyId::get())?;
+		let decimals = T::CurrencyRegistry::decimals(currency_id)?;
+		let amount = price.saturating_mul_int(fee);
+		let converted = convert_decimals(amount, native_decimals, decimals)?;
+		if convert_decimals(converted, decimals, native_decimals)? < amount {
+			converted.checked_add(1)
+		} else {
+			Some(converted)
+		}
+	}
+}
+
+/// Pays transaction fees with the currency chosen by `set_fee_currency`, falling
+/// back to the native adapter `N` for accounts paying in the native currency.
+///
+/// A `set_fee_currency` call itself is paid with the currency it chooses.
+pub struct MultiCurrencyAdapter<T, N>(PhantomData<(T, N)>);
+
+impl<T, N> OnChargeTransaction<T> for MultiCurrencyAdapter<T, N>
+where
+	T: Config + pallet_transaction_payment::Config,
+	<T as frame_system::Config>::Call: IsSubType<Call<T>>,
+	N: OnChargeTransaction<T, Balance = Balance>,
+{
+	type Balance = Balance;
+	type LiquidityInfo = FeeLiquidity<N::LiquidityInfo, CurrencyIdOf<T>>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &T::Call,
+		info: &DispatchInfoOf<T::Call>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let currency_id = match call.is_sub_type() {
+			Some(Call::set_fee_currency(currency_id)) => *currency_id,
+			_ => Pallet::<T>::fee_currencies(who),
+		};
+		let currency_id = match currency_id {
+			Some(currency_id) if currency_id != T::GetNativeCurrencyId::get() && !fee.is_zero() => currency_id,
+			_ => return N::withdraw_fee(who, call, info, fee, tip).map(FeeLiquidity::Native),
+		};
+
+		T::CurrencyRegistry::ensure_active(currency_id).map_err(|_| InvalidTransaction::Payment)?;
+		let price = Pallet::<T>::price_of(currency_id).ok_or(InvalidTransaction::Payment)?;
+		let amount = Pallet::<T>::convert_fee(currency_id, price, fee).ok_or(InvalidTransaction::Payment)?;
+		T::MultiCurrency::withdraw(currency_id, who, amount).map_err(|_| InvalidTransaction::Payment)?;
+		Ok(FeeLiquidity::Token { currency_id, amount, price })
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		dispatch_info: &DispatchInfoOf<T::Call>,
+		post_info: &PostDispatchInfoOf<T::Call>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		match already_withdrawn {
+			FeeLiquidity::Native(paid) => {
+				N::correct_and_deposit_fee(who, dispatch_info, post_info, corrected_fee, tip, paid)
+			},
+			FeeLiquidity::Token { currency_id, amount, price } => {
+				// convert with the price the fee was paid at, never charging more than paid.
+				let corrected_amount = Pallet::<T>::convert_fee(currency_id, price, corrected_fee)
+					.map_or(amount, |corrected_amount| corrected_amount.min(amount));
+				let refund_amount = amount.saturating_sub(corrected_amount);
+				T::MultiCurrency::deposit(currency_id, who, refund_amount).map_err(|_| InvalidTransaction::Payment)?;
+				T::MultiCurrency::deposit(currency_id, &T::FeeReceiver::get(), corrected_amount)
+					.map_err(|_| InvalidTransaction::Payment)?;
+				Ok(())
+			},
+		}
+	}
+}
@@ -0,0 +1,224 @@
+#![cfg(test)]
+
+use super::*;
+
+use crate as nftmart_fee_currency;
+use frame_support::{construct_runtime, parameter_types, weights::IdentityFee};
+use orml_currencies::BasicCurrencyAdapter;
+use orml_traits::DataProvider;
+use sp_core::{constants_types::*, H256};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u64;
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Module<Runtime>;
+	type MaxLocks = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExemptModuleAccounts: bool = true;
+	pub const MaxReserves: u32 = 50;
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = CurrencyRegistry;
+	type OnDust = ();
+	type ExemptModuleAccounts = ExemptModuleAccounts;
+	type ReserveIdentifier = [u8; 8];
+	type MaxReserves = MaxReserves;
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
+}
+
+pub type AdaptedBasicCurrency = BasicCurrencyAdapter<Runtime, Balances, Amount, Moment>;
+
+impl orml_currencies::Config for Runtime {
+	type Event = Event;
+	type MultiCurrency = Tokens;
+	type NativeCurrency = AdaptedBasicCurrency;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type IssuerOrigin = frame_system::EnsureRoot<AccountId>;
+	type IssuableCurrencies = CurrencyRegistry;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const NativeDecimals: u8 = 12;
+	pub const StringLimit: u32 = 32;
+}
+
+impl nftmart_currency_registry::Config for Runtime {
+	type Event = Event;
+	type CurrencyId = CurrencyId;
+	type Balance = Balance;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type NativeDecimals = NativeDecimals;
+	type RegistryOrigin = frame_system::EnsureRoot<AccountId>;
+	type StringLimit = StringLimit;
+	type InitialCurrencies = ();
+}
+
+parameter_types! {
+	pub const TransactionByteFee: Balance = 1;
+}
+
+impl pallet_transaction_payment::Config for Runtime {
+	type OnChargeTransaction = MultiCurrencyAdapter<Runtime, pallet_transaction_payment::CurrencyAdapter<Balances, ()>>;
+	type TransactionByteFee = TransactionByteFee;
+	type WeightToFee = IdentityFee<Balance>;
+	type FeeMultiplierUpdate = ();
+}
+
+/// One native unit is worth two `CURRENCY_ID` or `CURRENCY_ID_SIX_DECIMALS`, `CURRENCY_ID_NO_PRICE` has no price.
+pub struct MockPriceSource;
+impl DataProvider<CurrencyId, Price> for MockPriceSource {
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		match *currency_id {
+			NATIVE_CURRENCY_ID => Some(Price::saturating_from_integer(2)),
+			CURRENCY_ID | CURRENCY_ID_SIX_DECIMALS => Some(Price::saturating_from_integer(1)),
+			_ => None,
+		}
+	}
+}
+
+parameter_types! {
+	pub const FeeReceiver: AccountId = TREASURY;
+}
+
+impl nftmart_fee_currency::Config for Runtime {
+	type Event = Event;
+	type MultiCurrency = Currencies;
+	type CurrencyRegistry = CurrencyRegistry;
+	type PriceProvider = orml_traits::DefaultPriceProvider<CurrencyId, MockPriceSource>;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type FeeReceiver = FeeReceiver;
+}
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, u32, ()>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Module, Storage},
+		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
+		Currencies: orml_currencies::{Module, Call, Storage, Event<T>},
+		CurrencyRegistry: nftmart_currency_registry::{Module, Call, Storage, Event<T>, Config<T>},
+		FeeCurrency: nftmart_fee_currency::{Module, Call, Storage, Event<T>},
+	}
+);
+
+pub const ALICE: AccountId = 1;
+pub const TREASURY: AccountId = 2;
+pub const BOB: AccountId = 3;
+pub const CURRENCY_ID: CurrencyId = 1;
+pub const CURRENCY_ID_NO_PRICE: CurrencyId = 2;
+pub const CURRENCY_ID_SIX_DECIMALS: CurrencyId = 3;
+pub const CURRENCY_ID_NOT_EXIST: CurrencyId = 100;
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 1000)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			endowed_accounts: vec![
+				(ALICE, CURRENCY_ID, 1000),
+				(ALICE, CURRENCY_ID_NO_PRICE, 1000),
+				(ALICE, CURRENCY_ID_SIX_DECIMALS, 1000),
+				(BOB, CURRENCY_ID, 1000),
+			],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		nftmart_currency_registry::GenesisConfig::<Runtime> {
+			currencies: vec![
+				(CURRENCY_ID, b"Token".to_vec(), b"TKN".to_vec(), 12, 0),
+				(CURRENCY_ID_NO_PRICE, b"Other".to_vec(), b"OTH".to_vec(), 12, 0),
+				(CURRENCY_ID_SIX_DECIMALS, b"Six".to_vec(), b"SIX".to_vec(), 6, 0),
+			],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
+
+pub fn last_event() -> Event {
+	frame_system::Module::<Runtime>::events()
+		.pop()
+		.expect("Event expected")
+		.event
+}
@@ -0,0 +1,110 @@
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, weights::{DispatchInfo, PostDispatchInfo}};
+use mock::{CurrencyRegistry, Event, *};
+
+type Adapter = <Runtime as pallet_transaction_payment::Config>::OnChargeTransaction;
+
+fn token_balance(who: &AccountId) -> Balance {
+	<Currencies as MultiCurrency<AccountId>>::free_balance(CURRENCY_ID, who)
+}
+
+fn charge_call(who: AccountId, call: &Call, fee: Balance, corrected_fee: Balance) -> Result<(), TransactionValidityError> {
+	let info = DispatchInfo::default();
+	let paid = Adapter::withdraw_fee(&who, call, &info, fee, 0)?;
+	Adapter::correct_and_deposit_fee(&who, &info, &PostDispatchInfo::default(), corrected_fee, 0, paid)
+}
+
+fn charge(fee: Balance, corrected_fee: Balance) -> Result<(), TransactionValidityError> {
+	charge_call(ALICE, &Call::System(frame_system::Call::remark(vec![])), fee, corrected_fee)
+}
+
+#[test]
+fn set_fee_currency_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FeeCurrency::set_fee_currency(Origin::signed(ALICE), Some(CURRENCY_ID)));
+		assert_eq!(last_event(), Event::nftmart_fee_currency(crate::Event::FeeCurrencySet(ALICE, Some(CURRENCY_ID))));
+		assert_eq!(FeeCurrency::fee_currencies(ALICE), Some(CURRENCY_ID));
+
+		assert_ok!(FeeCurrency::set_fee_currency(Origin::signed(ALICE), Some(NATIVE_CURRENCY_ID)));
+		assert_eq!(last_event(), Event::nftmart_fee_currency(crate::Event::FeeCurrencySet(ALICE, None)));
+		assert_eq!(FeeCurrency::fee_currencies(ALICE), None);
+
+		assert_noop!(
+			FeeCurrency::set_fee_currency(Origin::signed(ALICE), Some(CURRENCY_ID_NOT_EXIST)),
+			nftmart_currency_registry::Error::<Runtime>::CurrencyNotRegistered,
+		);
+		assert_noop!(
+			FeeCurrency::set_fee_currency(Origin::signed(ALICE), Some(CURRENCY_ID_NO_PRICE)),
+			Error::<Runtime>::PriceNotFound,
+		);
+	});
+}
+
+#[test]
+fn pay_fee_in_native_currency_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(charge(100, 60));
+		assert_eq!(Balances::free_balance(ALICE), 940);
+		assert_eq!(token_balance(&ALICE), 1000);
+	});
+}
+
+#[test]
+fn pay_fee_in_token_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FeeCurrency::set_fee_currency(Origin::signed(ALICE), Some(CURRENCY_ID)));
+		assert_ok!(charge(100, 60));
+		assert_eq!(Balances::free_balance(ALICE), 1000);
+		assert_eq!(token_balance(&ALICE), 880);
+		assert_eq!(token_balance(&TREASURY), 120);
+
+		assert_eq!(charge(600, 600), Err(InvalidTransaction::Payment.into()));
+		assert_eq!(token_balance(&ALICE), 880);
+
+		assert_ok!(CurrencyRegistry::deprecate_currency(Origin::root(), CURRENCY_ID));
+		assert_eq!(charge(100, 100), Err(InvalidTransaction::Payment.into()));
+	});
+}
+
+#[test]
+fn pay_fee_in_token_with_other_decimals_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let balance = |who| <Currencies as MultiCurrency<AccountId>>::free_balance(CURRENCY_ID_SIX_DECIMALS, who);
+		assert_ok!(FeeCurrency::set_fee_currency(Origin::signed(ALICE), Some(CURRENCY_ID_SIX_DECIMALS)));
+
+		// 2_000_000 native units at 12 decimals are worth 4 units at 6 decimals, half of it is refunded.
+		assert_ok!(charge(2_000_000, 1_000_000));
+		assert_eq!(balance(&ALICE), 998);
+		assert_eq!(balance(&TREASURY), 2);
+
+		// fractions of the smallest unit are rounded up.
+		assert_ok!(charge(100, 100));
+		assert_eq!(balance(&ALICE), 997);
+		assert_eq!(balance(&TREASURY), 3);
+	});
+}
+
+#[test]
+fn choose_fee_currency_without_native_balance_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let remark = Call::System(frame_system::Call::remark(vec![]));
+		assert_eq!(Balances::free_balance(BOB), 0);
+		assert_eq!(charge_call(BOB, &remark, 100, 60), Err(InvalidTransaction::Payment.into()));
+
+		// choosing the fee currency is paid with it.
+		let set_fee_currency = Call::FeeCurrency(crate::Call::set_fee_currency(Some(CURRENCY_ID)));
+		assert_ok!(charge_call(BOB, &set_fee_currency, 100, 60));
+		assert_eq!(token_balance(&BOB), 880);
+		assert_ok!(FeeCurrency::set_fee_currency(Origin::signed(BOB), Some(CURRENCY_ID)));
+
+		assert_ok!(charge_call(BOB, &remark, 100, 60));
+		assert_eq!(token_balance(&BOB), 760);
+		assert_eq!(token_balance(&TREASURY), 240);
+
+		// going back to the native currency is paid with the native currency.
+		let set_native = Call::FeeCurrency(crate::Call::set_fee_currency(None));
+		assert_eq!(charge_call(BOB, &set_native, 100, 60), Err(InvalidTransaction::Payment.into()));
+	});
+}
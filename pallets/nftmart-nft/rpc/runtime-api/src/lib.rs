@@ -1,17 +1,31 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-pub use sp_core::constants_types::Balance;
+use codec::Codec;
+pub use sp_core::constants_types::{Balance, ClassId, CurrencyId, Moment, TokenId};
 pub use sp_std::vec::Vec;
-pub use nftmart_nft::NFTMetadata;
+pub use nftmart_nft::{CallGroup, NFTMetadata};
 
 sp_api::decl_runtime_apis! {
 	/// The helper API to calculate deposit.
-	pub trait NFTMartApi {
+	///
+	/// Version 2 adds the prices, the remaining supply and the paused call groups.
+	#[api_version(2)]
+	pub trait NFTMartApi<AccountId> where
+		AccountId: Codec,
+	{
 		/// mint_token_deposit
 		fn mint_token_deposit(metadata_len: u32, quantity: u32) -> (Balance, Balance);
 		/// add_class_admin_deposit
 		fn add_class_admin_deposit(admin_count: u32) -> Balance;
 		/// create_class_deposit
 		fn create_class_deposit(metadata_len: u32, name_len: u32, description_len: u32) -> (Balance, Balance);
+		/// The price of the order of `owner`, in `quote` currency, with the price timestamp.
+		fn order_price(class_id: ClassId, token_id: TokenId, owner: AccountId, quote: CurrencyId) -> Option<(Balance, Moment)>;
+		/// The lowest order price in `class_id`, in `quote` currency, with the price timestamp.
+		fn class_floor_price(class_id: ClassId, quote: CurrencyId) -> Option<(Balance, Moment)>;
+		/// The number of tokens which can still be minted in `class_id`, `None` if unlimited or not found.
+		fn remaining_supply(class_id: ClassId) -> Option<TokenId>;
+		/// The groups of marketplace calls which are currently paused.
+		fn paused_call_groups() -> Vec<CallGroup>;
 	}
 }
@@ -1,13 +1,15 @@
 use std::sync::Arc;
+use codec::Codec;
 use sp_blockchain::HeaderBackend;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 use sp_runtime::{generic::BlockId, traits::{Block as BlockT}};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 pub use nftmart_rpc_runtime_api::NFTMartApi as NFTMartRuntimeApi;
+use nftmart_rpc_runtime_api::{CallGroup, ClassId, CurrencyId, Moment, TokenId};
 
 #[rpc]
-pub trait NFTMartApi {
+pub trait NFTMartApi<AccountId> {
 	#[rpc(name = "nftmart_mintTokenDeposit")]
 	fn mint_token_deposit(&self, metadata_len: u32, quantity: u32) -> Result<(String, String)>;
 
@@ -16,6 +18,18 @@ pub trait NFTMartApi {
 
 	#[rpc(name = "nftmart_addClassAdminDeposit")]
 	fn add_class_admin_deposit(&self, admin_count: u32) -> Result<String>;
+
+	#[rpc(name = "nftmart_orderPrice")]
+	fn order_price(&self, class_id: ClassId, token_id: TokenId, owner: AccountId, quote: CurrencyId) -> Result<Option<(String, Moment)>>;
+
+	#[rpc(name = "nftmart_classFloorPrice")]
+	fn class_floor_price(&self, class_id: ClassId, quote: CurrencyId) -> Result<Option<(String, Moment)>>;
+
+	#[rpc(name = "nftmart_remainingSupply")]
+	fn remaining_supply(&self, class_id: ClassId) -> Result<Option<TokenId>>;
+
+	#[rpc(name = "nftmart_pausedCallGroups")]
+	fn paused_call_groups(&self) -> Result<Vec<CallGroup>>;
 }
 
 /// A struct that implements the [`NFTMartApi`].
@@ -48,11 +62,33 @@ impl From<Error> for i64 {
 	}
 }
 
-impl<C, Block> NFTMartApi for NFTMart<C, Block>
+/// Fail unless the runtime at `at` implements version 2 of the runtime api.
+fn ensure_api_version<Api, Block, AccountId>(api: &Api, at: &BlockId<Block>) -> Result<()>
+where
+	Block: BlockT,
+	Api: ApiExt<Block>,
+	AccountId: Codec,
+{
+	let supported = api
+		.has_api_with::<dyn NFTMartRuntimeApi<Block, AccountId>, _>(at, |version| version >= 2)
+		.unwrap_or(false);
+	if supported {
+		Ok(())
+	} else {
+		Err(RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "The runtime does not support this query.".into(),
+			data: None,
+		})
+	}
+}
+
+impl<C, Block, AccountId> NFTMartApi<AccountId> for NFTMart<C, Block>
 where
 	Block: BlockT,
 	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: NFTMartRuntimeApi<Block>,
+	C::Api: NFTMartRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
 {
 	/*
 		$ curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d   '{
@@ -101,4 +137,61 @@ where
 			format!("{}", deposit)
 		})
 	}
+
+	/*
+		$ curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d   '{
+		 "jsonrpc":"2.0",
+		  "id":1,
+		  "method":"nftmart_orderPrice",
+		  "params": [0, 0, "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", 1]
+		}'
+		{"jsonrpc":"2.0","result":["2000000000000",1618000000000],"id":1}
+	 */
+	fn order_price(&self, class_id: ClassId, token_id: TokenId, owner: AccountId, quote: CurrencyId) -> Result<Option<(String, Moment)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		ensure_api_version::<_, _, AccountId>(&*api, &at)?;
+		api.order_price(&at, class_id, token_id, owner, quote).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query order price.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(|price| {
+			price.map(|(amount, timestamp)| (format!("{}", amount), timestamp))
+		})
+	}
+
+	fn class_floor_price(&self, class_id: ClassId, quote: CurrencyId) -> Result<Option<(String, Moment)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		ensure_api_version::<_, _, AccountId>(&*api, &at)?;
+		api.class_floor_price(&at, class_id, quote).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query class floor price.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(|price| {
+			price.map(|(amount, timestamp)| (format!("{}", amount), timestamp))
+		})
+	}
+
+	fn remaining_supply(&self, class_id: ClassId) -> Result<Option<TokenId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		ensure_api_version::<_, _, AccountId>(&*api, &at)?;
+		api.remaining_supply(&at, class_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query remaining supply.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn paused_call_groups(&self) -> Result<Vec<CallGroup>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		ensure_api_version::<_, _, AccountId>(&*api, &at)?;
+		api.paused_call_groups(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query paused call groups.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
 }
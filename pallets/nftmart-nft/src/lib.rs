@@ -3,18 +3,22 @@
 use enumflags2::BitFlags;
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, ReservableCurrency, ExistenceRequirement::KeepAlive},
+	traits::{Currency, IsSubType, Randomness, ReservableCurrency, ExistenceRequirement::KeepAlive},
 	transactional, dispatch::DispatchResult
 };
 use sp_std::vec::Vec;
 use frame_system::pallet_prelude::*;
-use orml_traits::{MultiCurrency, MultiReservableCurrency};
-use sp_core::constants_types::{Balance, ACCURACY};
+use orml_traits::{MultiCurrency, MultiReservableCurrency, NamedMultiReservableCurrency, NFT};
+use nftmart_currency_registry::CurrencyRegistry;
+use nftmart_config::{MarketParameter, MarketParameters, PausedCalls, Whitelist, WhitelistPermission};
+pub use nftmart_config::CallGroup;
+use sp_core::{H256, constants_types::{Balance, ReserveIdentifier, TokenId, ACCURACY}};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{CheckedAdd, Bounded,
+	traits::{CheckedAdd, CheckedMul, Bounded, DispatchInfoOf, SignedExtension, BlakeTwo256, Hash,
 			 AccountIdConversion, StaticLookup, Zero, One, AtLeast32BitUnsigned},
+	transaction_validity::{InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction},
 	ModuleId, RuntimeDebug, SaturatedConversion,
 };
 use codec::FullCodec;
@@ -66,6 +70,8 @@ pub struct ClassData<BlockNumber> {
 	pub description: Vec<u8>,
 	#[codec(compact)]
 	pub create_block: BlockNumber,
+	/// The maximum number of tokens ever minted in this class, unlimited if `None`.
+	pub max_supply: Option<TokenId>,
 }
 
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
@@ -106,10 +112,176 @@ pub struct OrderData<T: Config> {
 	/// Category of this order.
 	#[codec(compact)]
 	pub category_id: CategoryIdOf<T>,
+	/// Accounts allowed to take this order, anyone if empty.
+	pub takers: Vec<T::AccountId>,
 	// TODO: Add `is_token_owner` field.
 }
 
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ClassOfferData<T: Config> {
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Price paid for each token.
+	#[codec(compact)]
+	pub price: Balance,
+	/// The number of tokens still wanted.
+	#[codec(compact)]
+	pub quantity: u32,
+	/// The balances to create an offer
+	#[codec(compact)]
+	pub deposit: Balance,
+	/// This offer will be invalidated after `deadline` block number.
+	#[codec(compact)]
+	pub deadline: BlockNumberOf<T>,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BundleOrderData<T: Config> {
+	/// The account selling the bundle.
+	pub owner: T::AccountId,
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Price of the whole bundle.
+	#[codec(compact)]
+	pub price: Balance,
+	/// The balances to create an order
+	#[codec(compact)]
+	pub deposit: Balance,
+	/// This order will be invalidated after `deadline` block number.
+	#[codec(compact)]
+	pub deadline: BlockNumberOf<T>,
+	/// Tokens sold together in this order.
+	pub tokens: Vec<(ClassIdOf<T>, TokenIdOf<T>)>,
+}
+
+/// What a swap offer wants in exchange.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SwapTarget<ClassId, TokenId> {
+	/// A specific token.
+	Token(ClassId, TokenId),
+	/// Any token of a class.
+	AnyOf(ClassId),
+}
+
+impl<ClassId: PartialEq, TokenId: PartialEq> SwapTarget<ClassId, TokenId> {
+	/// Whether `token` satisfies this target.
+	pub fn matches(&self, token: &(ClassId, TokenId)) -> bool {
+		match self {
+			SwapTarget::Token(class_id, token_id) => *class_id == token.0 && *token_id == token.1,
+			SwapTarget::AnyOf(class_id) => *class_id == token.0,
+		}
+	}
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapOfferData<T: Config> {
+	/// The account making the offer.
+	pub owner: T::AccountId,
+	/// Tokens given away by `owner`.
+	pub tokens: Vec<(ClassIdOf<T>, TokenIdOf<T>)>,
+	/// Currency paid by `owner` on top of `tokens`.
+	pub top_up: Option<(CurrencyIdOf<T>, Balance)>,
+	/// The token wanted in exchange.
+	pub target: SwapTarget<ClassIdOf<T>, TokenIdOf<T>>,
+	/// The balances to create an offer
+	#[codec(compact)]
+	pub deposit: Balance,
+	/// This offer will be invalidated after `deadline` block number.
+	#[codec(compact)]
+	pub deadline: BlockNumberOf<T>,
+}
+
+/// The accounts allowed to claim in a sale phase.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SaleAllowlist<AccountId> {
+	/// Accounts proven by a Merkle proof against this root.
+	///
+	/// Leaves are `blake2_256(account.encode())`, pairs are hashed in sorted order.
+	MerkleRoot(H256),
+	/// Accounts listed explicitly.
+	Accounts(Vec<AccountId>),
+}
+
+impl<AccountId: Encode + PartialEq> SaleAllowlist<AccountId> {
+	/// Whether `who` is allowed by this list, given the Merkle `proof` if any.
+	pub fn allows(&self, who: &AccountId, proof: &[H256]) -> bool {
+		match self {
+			SaleAllowlist::MerkleRoot(root) => {
+				let leaf = BlakeTwo256::hash_of(who);
+				let computed = proof.iter().fold(leaf, |node, sibling| {
+					if node <= *sibling {
+						BlakeTwo256::hash_of(&(node, sibling))
+					} else {
+						BlakeTwo256::hash_of(&(sibling, node))
+					}
+				});
+				computed == *root
+			},
+			SaleAllowlist::Accounts(accounts) => accounts.contains(who),
+		}
+	}
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SalePhase<T: Config> {
+	/// The phase opens at this block number.
+	#[codec(compact)]
+	pub start: BlockNumberOf<T>,
+	/// The phase closes at this block number.
+	#[codec(compact)]
+	pub end: BlockNumberOf<T>,
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Price paid for each token.
+	#[codec(compact)]
+	pub price: Balance,
+	/// The maximum number of tokens an account can claim in this phase.
+	#[codec(compact)]
+	pub per_account_cap: u32,
+	/// Accounts allowed to claim in this phase, anyone if `None`.
+	pub allowlist: Option<SaleAllowlist<T::AccountId>>,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PrimarySaleData<T: Config> {
+	/// Metadata of the tokens minted by the sale.
+	pub metadata: NFTMetadata,
+	/// Phases of the sale.
+	pub phases: Vec<SalePhase<T>>,
+}
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BlindBoxData<T: Config> {
+	/// currency ID.
+	#[codec(compact)]
+	pub currency_id: CurrencyIdOf<T>,
+	/// Price paid for each box.
+	#[codec(compact)]
+	pub price: Balance,
+	/// Metadata of the sold tokens until they are revealed.
+	pub placeholder: NFTMetadata,
+	/// Sold tokens are revealed from this block number, or as soon as their seed is recorded if `None`.
+	pub reveal_at: Option<BlockNumberOf<T>>,
+	/// Metadata of the items not drawn yet.
+	pub items: Vec<NFTMetadata>,
+	/// The sold tokens waiting for their reveal in the order of purchase,
+	/// with the randomness round they were bought in.
+	pub unrevealed: Vec<(TokenIdOf<T>, u32)>,
+}
+
 pub type NFTMetadata = Vec<u8>;
+pub type OrderId = u64;
 pub type TokenIdOf<T> = <T as orml_nft::Config>::TokenId;
 pub type ClassIdOf<T> = <T as orml_nft::Config>::ClassId;
 pub type CategoryIdOf<T> = <T as Config>::CategoryId;
@@ -117,21 +289,32 @@ pub type BalanceOf<T> = <<T as module::Config>::Currency as Currency<<T as frame
 pub type CurrencyIdOf<T> = <<T as module::Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
 pub type BlockNumberOf<T> = <T as frame_system::Config>::BlockNumber;
 
+/// The named reserve holding the deposits and prices of the marketplace.
+pub const RESERVE_ID: ReserveIdentifier = *b"nftmart/";
+
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
 enum Releases {
 	V1_0_0,
 	V2_0_0,
+	V3_0_0,
+	V4_0_0,
+	V5_0_0,
+	V6_0_0,
+	V7_0_0,
 }
 
 impl Default for Releases {
 	fn default() -> Self {
-		Releases::V2_0_0
+		Releases::V7_0_0
 	}
 }
 
 pub mod migrations {
 	use super::*;
 
+	/// The token ids counted into the token balances per block, each costing at most two reads and a write.
+	pub const TOKEN_BALANCES_PER_BLOCK: u32 = 500;
+
 	#[derive(Decode)]
 	pub struct OldClassData {
 		#[codec(compact)]
@@ -141,16 +324,54 @@ pub mod migrations {
 		pub description: Vec<u8>,
 	}
 
+	#[derive(Encode, Decode)]
+	pub struct ClassDataV4<BlockNumber> {
+		#[codec(compact)]
+		pub deposit: Balance,
+		pub properties: Properties,
+		pub name: Vec<u8>,
+		pub description: Vec<u8>,
+		#[codec(compact)]
+		pub create_block: BlockNumber,
+	}
+
 	#[derive(Decode)]
 	pub struct OldTokenData {
 		#[codec(compact)]
 		pub deposit: Balance,
 	}
 
+	#[derive(Decode)]
+	pub struct OldOrderData<T: Config> {
+		#[codec(compact)]
+		pub currency_id: CurrencyIdOf<T>,
+		#[codec(compact)]
+		pub price: Balance,
+		#[codec(compact)]
+		pub deposit: Balance,
+		#[codec(compact)]
+		pub deadline: BlockNumberOf<T>,
+		#[codec(compact)]
+		pub category_id: CategoryIdOf<T>,
+	}
+
+	impl<T: Config> OldOrderData<T> {
+		fn upgraded(self) -> OrderData<T> {
+			OrderData {
+				currency_id: self.currency_id,
+				price: self.price,
+				deposit: self.deposit,
+				deadline: self.deadline,
+				category_id: self.category_id,
+				takers: Vec::new(),
+			}
+		}
+	}
+
 	impl OldClassData {
-		fn upgraded<T>(self) -> ClassData<T> where T: AtLeast32BitUnsigned + Bounded + Copy + From<u32> {
+		fn upgraded<T>(self) -> ClassDataV4<T> where T: AtLeast32BitUnsigned + Bounded + Copy + From<u32> {
 			let create_block: T = One::one();
-			ClassData {
+			ClassDataV4 {
 				create_block: create_block * 2u32.into(),
 				deposit: self.deposit,
 				properties: self.properties,
@@ -160,6 +381,19 @@ pub mod migrations {
 		}
 	}
 
+	impl<BlockNumber> ClassDataV4<BlockNumber> {
+		fn upgraded(self) -> ClassData<BlockNumber> {
+			ClassData {
+				deposit: self.deposit,
+				properties: self.properties,
+				name: self.name,
+				description: self.description,
+				create_block: self.create_block,
+				max_supply: None,
+			}
+		}
+	}
+
 	impl OldTokenData {
 		fn upgraded<T>(self) -> TokenData<T> where T: AtLeast32BitUnsigned + Bounded + Copy + From<u32> {
 			let create_block: T = One::one();
@@ -178,7 +412,7 @@ pub mod migrations {
 				 metadata: p.metadata,
 				 total_issuance: p.total_issuance,
 				 owner: p.owner,
-				 data: p.data.upgraded::<BlockNumberOf<T>>(),
+				 data: p.data.upgraded::<BlockNumberOf<T>>().upgraded(),
 			};
 			Some(new_data)
 		});
@@ -194,6 +428,87 @@ pub mod migrations {
 		});
 		T::BlockWeights::get().max_block
 	}
+
+	pub fn migrate_max_supply<T: Config>() -> Weight {
+		type OldClass<T> = orml_nft::ClassInfo<TokenIdOf<T>, <T as frame_system::Config>::AccountId, ClassDataV4<BlockNumberOf<T>>>;
+		orml_nft::Classes::<T>::translate::<OldClass<T>, _>(|_, p: OldClass<T>| {
+			Some(orml_nft::ClassInfo {
+				metadata: p.metadata,
+				total_issuance: p.total_issuance,
+				owner: p.owner,
+				data: p.data.upgraded(),
+			})
+		});
+		T::BlockWeights::get().max_block
+	}
+
+	pub fn migrate_orders<T: Config>() -> Weight {
+		Orders::<T>::translate::<OldOrderData<T>, _>(|_, _, p: OldOrderData<T>| {
+			Some(p.upgraded())
+		});
+		T::BlockWeights::get().max_block
+	}
+
+	/// Move `amount` of the anonymous reserve of `who` into `RESERVE_ID`.
+	///
+	/// Leaves the anonymous reserve untouched and returns `false` if it can not be named.
+	fn name_reserve<T: Config>(currency_id: CurrencyIdOf<T>, who: &T::AccountId, amount: Balance) -> bool {
+		frame_support::storage::with_transaction(|| {
+			let actual = amount.saturating_sub(T::MultiCurrency::unreserve(currency_id, who, amount));
+			match T::MultiCurrency::reserve_named(&RESERVE_ID, currency_id, who, actual) {
+				Ok(()) => sp_runtime::TransactionOutcome::Commit(true),
+				Err(_) => sp_runtime::TransactionOutcome::Rollback(false),
+			}
+		})
+	}
+
+	/// Orders and classes are the only reserves existing before `V4_0_0`.
+	pub fn migrate_named_reserves<T: Config>() -> Weight {
+		let native_currency_id = T::GetNativeCurrencyId::get();
+		let mut reads: Weight = 0;
+		let mut named: Weight = 0;
+		let mut failed: u32 = 0;
+		let mut name = |currency_id, who: &T::AccountId, amount| {
+			named = named.saturating_add(1);
+			if !name_reserve::<T>(currency_id, who, amount) {
+				failed = failed.saturating_add(1);
+			}
+		};
+		for ((class_id, token_id), who, order) in Orders::<T>::iter() {
+			reads = reads.saturating_add(2);
+			name(native_currency_id, &who, order.deposit);
+			if !orml_nft::Module::<T>::is_owner(&who, (class_id, token_id)) {
+				name(order.currency_id, &who, order.price);
+			}
+		}
+		for (class_id, class_info) in orml_nft::Classes::<T>::iter() {
+			reads = reads.saturating_add(1);
+			name(native_currency_id, &class_info.owner, class_info.data.deposit);
+			for (_, token_info) in orml_nft::Tokens::<T>::iter_prefix(class_id) {
+				reads = reads.saturating_add(1);
+				name(native_currency_id, &class_info.owner, token_info.data.deposit);
+			}
+		}
+		if failed > 0 {
+			frame_support::log::warn!(
+				target: "runtime::nftmart",
+				"{} of {} reserves could not be named and stay anonymous",
+				failed,
+				named,
+			);
+		}
+		// each naming reads and writes the balance and the named reserves of the account.
+		T::DbWeight::get().reads_writes(reads.saturating_add(named.saturating_mul(2)), named.saturating_mul(2))
+	}
+
+	pub fn migrate_class_orders<T: Config>() -> Weight {
+		let mut count: Weight = 0;
+		for ((class_id, token_id), who, _) in Orders::<T>::iter() {
+			ClassOrders::<T>::insert(class_id, (token_id, who), ());
+			count = count.saturating_add(1);
+		}
+		T::DbWeight::get().reads_writes(count.saturating_add(1), count)
+	}
 }
 
 #[frame_support::pallet]
@@ -204,35 +519,82 @@ pub mod module {
 	#[pallet::config]
 	pub trait Config: frame_system::Config +
 		orml_nft::Config<ClassData = ClassData<BlockNumberOf<Self>>, TokenData = TokenData<BlockNumberOf<Self>>> +
-		pallet_proxy::Config +
-		nftmart_config::Config
+		pallet_proxy::Config
 	{
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// The minimum balance to create class
-		#[pallet::constant]
-		type CreateClassDeposit: Get<Balance>;
-
-		/// The amount of balance that must be deposited per byte of metadata.
-		#[pallet::constant]
-		type MetaDataByteDeposit: Get<Balance>;
-
-		/// The minimum balance to create token
-		#[pallet::constant]
-		type CreateTokenDeposit: Get<Balance>;
-
 		/// The NFT's module id
 		#[pallet::constant]
 		type ModuleId: Get<ModuleId>;
 
-		/// MultiCurrency type for trading
-		type MultiCurrency: MultiReservableCurrency<Self::AccountId, Balance = Balance>;
+		/// MultiCurrency type for trading, deposits are reserved in its native currency.
+		type MultiCurrency: NamedMultiReservableCurrency<Self::AccountId, Balance = Balance, ReserveIdentifier = ReserveIdentifier>;
+
+		/// The native currency id of `MultiCurrency`.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
 
 		/// The currency mechanism.
 		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// The Category ID type
 		type CategoryId: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaybeSerializeDeserialize + Bounded + FullCodec;
+
+		/// The origin which may create or update categories.
+		type CategoryOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum length of a category's metadata.
+		#[pallet::constant]
+		type MaxCategoryMetadataLen: Get<u32>;
+
+		/// The maximum number of tokens in a bundle order.
+		#[pallet::constant]
+		type MaxBundleSize: Get<u32>;
+
+		/// The maximum number of accounts an order can be reserved for.
+		#[pallet::constant]
+		type MaxTakers: Get<u32>;
+
+		/// Registry of the currencies accepted for trading.
+		type CurrencyRegistry: CurrencyRegistry<CurrencyIdOf<Self>>;
+
+		/// Whitelist of the accounts allowed to create classes and receive mints.
+		type Whitelist: Whitelist<Self::AccountId>;
+
+		/// The deposits of classes and tokens, tunable at runtime.
+		type MarketParameters: MarketParameters;
+
+		/// The paused groups of calls.
+		type PausedCalls: PausedCalls;
+
+		/// The maximum number of phases in a primary sale.
+		#[pallet::constant]
+		type MaxSalePhases: Get<u32>;
+
+		/// The maximum number of accounts listed explicitly in a sale phase.
+		#[pallet::constant]
+		type MaxSaleAllowlistLen: Get<u32>;
+
+		/// The maximum number of tokens claimed at once in a primary sale.
+		#[pallet::constant]
+		type MaxClaimQuantity: Get<u32>;
+
+		/// The maximum length of the Merkle proof of a sale allowlist.
+		#[pallet::constant]
+		type MaxSaleProofLen: Get<u32>;
+
+		/// The source of randomness drawing the items of blind boxes.
+		///
+		/// A seed is recorded each time the randomness changes, see `RandomnessRound`.
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+		/// The maximum number of items in the pool of a blind box.
+		#[pallet::constant]
+		type MaxBlindBoxItems: Get<u32>;
+
+		/// The maximum length of the metadata of a blind box item or placeholder.
+		#[pallet::constant]
+		type MaxBlindBoxItemLen: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -282,6 +644,64 @@ pub mod module {
 		DescriptionTooLong,
 		/// account not in whitelist
 		AccountNotInWhitelist,
+		/// Category metadata too long
+		CategoryMetadataTooLong,
+		/// Class offer not found
+		ClassOfferNotFound,
+		/// Bundle order not found
+		BundleOrderNotFound,
+		/// Bundle is empty, too large or contains duplicated tokens
+		InvalidBundle,
+		/// No available order ID
+		NoAvailableOrderId,
+		/// Swap offer not found
+		SwapOfferNotFound,
+		/// The token does not match what the swap offer wants.
+		SwapTargetMismatch,
+		/// The order is reserved for other accounts.
+		NotOrderTaker,
+		/// Too many takers for an order.
+		TooManyTakers,
+		/// Minting would exceed the max supply of the class.
+		MaxSupplyExceeded,
+		/// Blind box not found
+		BlindBoxNotFound,
+		/// The class already has a blind box.
+		BlindBoxExists,
+		/// Not enough items left in the blind box.
+		BlindBoxSoldOut,
+		/// Too many items in the pool of a blind box.
+		TooManyBlindBoxItems,
+		/// The metadata of a blind box item or placeholder is too long.
+		BlindBoxItemTooLong,
+		/// Sold tokens of the blind box are still waiting for their reveal.
+		UnrevealedTokensExist,
+		/// No token of the blind box is waiting for a reveal.
+		NothingToReveal,
+		/// The reveal block of the blind box is not reached, or the seed of the next token is not recorded yet.
+		RevealNotReady,
+		/// Primary sale not found
+		PrimarySaleNotFound,
+		/// Sale phases are empty, too many or invalid.
+		InvalidSalePhases,
+		/// Sale phase not found
+		SalePhaseNotFound,
+		/// The sale phase is not open.
+		SalePhaseNotActive,
+		/// The account is not allowed to claim in the sale phase.
+		NotInSaleAllowlist,
+		/// The per-account cap of the sale phase is exceeded.
+		SaleCapExceeded,
+		/// The Merkle proof of the sale allowlist is too long.
+		SaleProofTooLong,
+		/// The calls of the group are paused.
+		CallsPaused,
+		/// Duplicated class offer.
+		DuplicatedClassOffer,
+		/// Not allow to take own class offer.
+		TakeOwnClassOffer,
+		/// Class offer expired
+		ClassOfferExpired,
 	}
 
 	#[pallet::event]
@@ -311,6 +731,40 @@ pub mod module {
 		UpdatedOrderPrice(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, Balance),
 		/// OrderMinDeposit updated \[old, new\]
 		UpdatedMinOrderDeposit(Balance, Balance),
+		/// Created a class offer. \[class_id, offer_owner\]
+		CreatedClassOffer(ClassIdOf<T>, T::AccountId),
+		/// Removed a class offer. \[class_id, offer_owner, unreserved\]
+		RemovedClassOffer(ClassIdOf<T>, T::AccountId, Balance),
+		/// A class offer had been taken. \[class_id, token_id, offer_owner\]
+		TakenClassOffer(ClassIdOf<T>, TokenIdOf<T>, T::AccountId),
+		/// Created a bundle order. \[order_id, order_owner\]
+		CreatedBundleOrder(OrderId, T::AccountId),
+		/// Removed a bundle order. \[order_id, order_owner, unreserved\]
+		RemovedBundleOrder(OrderId, T::AccountId, Balance),
+		/// A bundle order had been taken. \[order_id, order_owner, taker\]
+		TakenBundleOrder(OrderId, T::AccountId, T::AccountId),
+		/// Created a swap offer. \[order_id, offer_owner\]
+		CreatedSwapOffer(OrderId, T::AccountId),
+		/// Removed a swap offer. \[order_id, offer_owner, unreserved\]
+		RemovedSwapOffer(OrderId, T::AccountId, Balance),
+		/// A swap offer had been taken. \[order_id, offer_owner, taker\]
+		TakenSwapOffer(OrderId, T::AccountId, T::AccountId),
+		/// Set the primary sale of a class. \[class_id\]
+		SetPrimarySale(ClassIdOf<T>),
+		/// Removed the primary sale of a class. \[class_id\]
+		RemovedPrimarySale(ClassIdOf<T>),
+		/// Claimed tokens in a primary sale. \[who, class_id, phase_index, quantity\]
+		ClaimedMint(T::AccountId, ClassIdOf<T>, u32, u32),
+		/// Created the blind box of a class. \[class_id\]
+		CreatedBlindBox(ClassIdOf<T>),
+		/// Added items to a blind box. \[class_id, count\]
+		AddedBlindBoxItems(ClassIdOf<T>, u32),
+		/// Removed the blind box of a class. \[class_id\]
+		RemovedBlindBox(ClassIdOf<T>),
+		/// Bought blind boxes. \[who, class_id, quantity\]
+		BoughtBlindBox(T::AccountId, ClassIdOf<T>, u32),
+		/// Revealed a token sold in a blind box. \[class_id, token_id\]
+		RevealedToken(ClassIdOf<T>, TokenIdOf<T>),
 	}
 
 	#[pallet::pallet]
@@ -318,13 +772,51 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let weight = orml_nft::Module::<T>::migrate_token_balances(migrations::TOKEN_BALANCES_PER_BLOCK);
+			let (seed, known_since) = T::Randomness::random(b"nftmart/blindbox");
+			let (round, last_known_since) = Self::randomness_round();
+			if known_since == last_known_since {
+				return weight.saturating_add(T::DbWeight::get().reads(1));
+			}
+			// the randomness of `seed` was not determinable during the round ending now.
+			RoundSeeds::<T>::insert(round, seed);
+			RandomnessRound::<T>::put((round.saturating_add(1), known_since));
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+		}
+
 		fn on_runtime_upgrade() -> Weight {
+			let mut weight: Weight = 0;
+			// `do_migrate` upgrades the classes to the latest `ClassData` at once.
+			let classes_upgraded = StorageVersion::<T>::get() == Releases::V1_0_0;
 			if StorageVersion::<T>::get() == Releases::V1_0_0 {
 				StorageVersion::<T>::put(Releases::V2_0_0);
-				migrations::do_migrate::<T>()
-			} else {
-				0
+				weight = weight.saturating_add(migrations::do_migrate::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V2_0_0 {
+				StorageVersion::<T>::put(Releases::V3_0_0);
+				weight = weight.saturating_add(migrations::migrate_orders::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V3_0_0 {
+				StorageVersion::<T>::put(Releases::V4_0_0);
+				weight = weight.saturating_add(migrations::migrate_named_reserves::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V4_0_0 {
+				StorageVersion::<T>::put(Releases::V5_0_0);
+				if !classes_upgraded {
+					weight = weight.saturating_add(migrations::migrate_max_supply::<T>());
+				}
+			}
+			if StorageVersion::<T>::get() == Releases::V5_0_0 {
+				StorageVersion::<T>::put(Releases::V6_0_0);
+				// the tokens are counted by `on_initialize`, `TOKEN_BALANCES_PER_BLOCK` at a time.
+				weight = weight.saturating_add(orml_nft::Module::<T>::start_token_balances_migration());
 			}
+			if StorageVersion::<T>::get() == Releases::V6_0_0 {
+				StorageVersion::<T>::put(Releases::V7_0_0);
+				weight = weight.saturating_add(migrations::migrate_class_orders::<T>());
+			}
+			weight
 		}
 
 		fn integrity_test () {}
@@ -373,6 +865,57 @@ pub mod module {
 	#[pallet::getter(fn orders)]
 	pub type Orders<T: Config> = StorageDoubleMap<_, Blake2_128Concat, (ClassIdOf<T>, TokenIdOf<T>), Blake2_128Concat, T::AccountId, OrderData<T>>;
 
+	/// An index mapping from class to the orders on any of its tokens.
+	#[pallet::storage]
+	pub type ClassOrders<T: Config> = StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Blake2_128Concat, (TokenIdOf<T>, T::AccountId), ()>;
+
+	/// An index mapping from class to the offers made on any of its tokens.
+	#[pallet::storage]
+	#[pallet::getter(fn class_offers)]
+	pub type ClassOffers<T: Config> = StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Blake2_128Concat, T::AccountId, ClassOfferData<T>>;
+
+	/// Next available ID for orders stored by ID.
+	#[pallet::storage]
+	#[pallet::getter(fn next_order_id)]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// The storage of bundle orders.
+	#[pallet::storage]
+	#[pallet::getter(fn bundle_orders)]
+	pub type BundleOrders<T: Config> = StorageMap<_, Twox64Concat, OrderId, BundleOrderData<T>>;
+
+	/// The storage of swap offers.
+	#[pallet::storage]
+	#[pallet::getter(fn swap_offers)]
+	pub type SwapOffers<T: Config> = StorageMap<_, Twox64Concat, OrderId, SwapOfferData<T>>;
+
+	/// The primary sales of classes.
+	#[pallet::storage]
+	#[pallet::getter(fn primary_sales)]
+	pub type PrimarySales<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, PrimarySaleData<T>>;
+
+	/// The number of tokens claimed by an account in a phase of a primary sale.
+	#[pallet::storage]
+	#[pallet::getter(fn sale_claims)]
+	pub type SaleClaims<T: Config> = StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Blake2_128Concat, (u32, T::AccountId), u32, ValueQuery>;
+
+	/// The blind boxes of classes.
+	#[pallet::storage]
+	#[pallet::getter(fn blind_boxes)]
+	pub type BlindBoxes<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, BlindBoxData<T>>;
+
+	/// The number of random seeds recorded, and the block since which the randomness of the last one was determinable.
+	#[pallet::storage]
+	#[pallet::getter(fn randomness_round)]
+	pub type RandomnessRound<T: Config> = StorageValue<_, (u32, BlockNumberOf<T>), ValueQuery>;
+
+	/// The random seed of a round, drawing the items of the blind boxes bought in the round.
+	///
+	/// It is recorded when the round ends, from randomness determined after the purchases of the round.
+	#[pallet::storage]
+	#[pallet::getter(fn round_seeds)]
+	pub type RoundSeeds<T: Config> = StorageMap<_, Twox64Concat, u32, T::Hash>;
+
 	/// Order deposit config
 	#[pallet::storage]
 	#[pallet::getter(fn min_order_deposit)]
@@ -395,6 +938,7 @@ pub mod module {
 			order_owner: T::AccountId,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			Self::ensure_trader(&who)?;
 			ensure!(order_owner != who, Error::<T>::TakeOwnOrder);
 
 			let order: OrderData<T> = {
@@ -404,6 +948,7 @@ pub mod module {
 			};
 
 			ensure!(<frame_system::Pallet<T>>::block_number() <= order.deadline, Error::<T>::OrderExpired);
+			ensure!(order.takers.is_empty() || order.takers.contains(&who), Error::<T>::NotOrderTaker);
 
 			let token_owner = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?.owner;
 			match (order_owner == token_owner, token_owner == who) {
@@ -414,7 +959,7 @@ pub mod module {
 					Self::try_delete_order(class_id, token_id, &who, &token_owner);
 					// `order_owner` transfers this NFT to `who`
 					Self::do_transfer(&order_owner, &who, class_id, token_id)?;
-					T::MultiCurrency::transfer(order.currency_id, &who, &order_owner, order.price)?;
+					T::MultiCurrency::transfer_keep_alive(order.currency_id, &who, &order_owner, order.price)?;
 					// TODO: T::MultiCurrency::transfer(order.currency_id, &order_owner, some_account,platform-fee)?;
 					Self::deposit_event(Event::TakenOrder(class_id, token_id, order_owner));
 				},
@@ -425,7 +970,7 @@ pub mod module {
 					Self::try_delete_order(class_id, token_id, &who, &token_owner);
 					// `order_owner` transfers this NFT to `who`
 					Self::do_transfer(&who, &order_owner, class_id, token_id)?;
-					T::MultiCurrency::transfer(order.currency_id, &order_owner, &who, order.price)?;
+					T::MultiCurrency::transfer_keep_alive(order.currency_id, &order_owner, &who, order.price)?;
 					// TODO: T::MultiCurrency::transfer(order.currency_id, &who, some_account,platform-fee)?;
 					Self::deposit_event(Event::TakenOrder(class_id, token_id, order_owner));
 				},
@@ -445,6 +990,7 @@ pub mod module {
 		/// - `token_id`: token id
 		/// - `deposit`: The balances to create an order
 		/// - `deadline`: deadline
+		/// - `takers`: accounts allowed to take this order, anyone if empty
 		#[pallet::weight(100_000)]
 		#[transactional]
 		pub fn submit_order(
@@ -456,9 +1002,13 @@ pub mod module {
 			#[pallet::compact] token_id: TokenIdOf<T>,
 			#[pallet::compact] deposit: Balance,
 			#[pallet::compact] deadline: BlockNumberOf<T>,
+			takers: Vec<T::AccountId>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			Self::ensure_trader(&who)?;
 			let token: TokenInfoOf<T> = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?;
+			ensure!(takers.len() <= T::MaxTakers::get() as usize, Error::<T>::TooManyTakers);
+			T::CurrencyRegistry::ensure_active(currency_id)?;
 
 			ensure!(Self::orders((class_id, token_id), &who).is_none(), Error::<T>::DuplicatedOrder);
 			ensure!(<frame_system::Pallet<T>>::block_number() < deadline, Error::<T>::InvalidDeadline);
@@ -469,11 +1019,11 @@ pub mod module {
 			})?;
 
 			ensure!(deposit >= Self::min_order_deposit(), Error::<T>::InvalidDeposit);
-			<T as Config>::Currency::reserve(&who, deposit.saturated_into())?;
+			Self::reserve_deposit(&who, deposit)?;
 
 			if token.owner != who {
 				ensure!(!Self::is_burnable(class_id)?, Error::<T>::Burnable); // TODO: Get ride of this limitation.
-				T::MultiCurrency::reserve(currency_id, &who, price.saturated_into())?;
+				T::MultiCurrency::reserve_named(&RESERVE_ID, currency_id, &who, price)?;
 			}
 
 			let order: OrderData<T> = OrderData {
@@ -482,8 +1032,10 @@ pub mod module {
 				deposit,
 				deadline,
 				category_id,
+				takers,
 			};
 			Orders::<T>::insert((class_id, token_id), &who, order);
+			ClassOrders::<T>::insert(class_id, (token_id, who.clone()), ());
 
 			Self::deposit_event(Event::CreatedOrder(class_id, token_id, who));
 			Ok(().into())
@@ -524,8 +1076,8 @@ pub mod module {
 				let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
 
 				if !orml_nft::Module::<T>::is_owner(&who, (class_id, token_id)) {
-					let _ = T::MultiCurrency::unreserve(order.currency_id, &who, order.price.saturated_into());
-					T::MultiCurrency::reserve(order.currency_id, &who, price.saturated_into())?;
+					let _ = T::MultiCurrency::unreserve_named(&RESERVE_ID, order.currency_id, &who, order.price);
+					T::MultiCurrency::reserve_named(&RESERVE_ID, order.currency_id, &who, price)?;
 				}
 
 				order.price = price;
@@ -535,58 +1087,353 @@ pub mod module {
 			Ok(().into())
 		}
 
-		/// Create a common category for trading NFT.
-		/// A Selling NFT should belong to a category.
+		/// Create an offer to buy any `quantity` tokens of a class. Create only.
 		///
-		/// - `metadata`: metadata
+		/// - `currency_id`: currency id
+		/// - `price`: price paid for each token
+		/// - `class_id`: class id
+		/// - `quantity`: the number of tokens to buy
+		/// - `deposit`: The balances to create an offer
+		/// - `deadline`: deadline
 		#[pallet::weight(100_000)]
 		#[transactional]
-		pub fn create_category(origin: OriginFor<T>, metadata: NFTMetadata) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+		pub fn submit_class_offer(
+			origin: OriginFor<T>,
+			#[pallet::compact] currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] price: Balance,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] quantity: u32,
+			#[pallet::compact] deposit: Balance,
+			#[pallet::compact] deadline: BlockNumberOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_trader(&who)?;
+			ensure!(orml_nft::Module::<T>::classes(class_id).is_some(), Error::<T>::ClassIdNotFound);
+			ensure!(Self::class_offers(class_id, &who).is_none(), Error::<T>::DuplicatedClassOffer);
+			T::CurrencyRegistry::ensure_active(currency_id)?;
+			ensure!(quantity >= 1, Error::<T>::InvalidQuantity);
+			ensure!(<frame_system::Pallet<T>>::block_number() < deadline, Error::<T>::InvalidDeadline);
 
-			let category_id = NextCategoryId::<T>::try_mutate(|id| -> Result<T::CategoryId, DispatchError> {
-				let current_id = *id;
-				*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableCategoryId)?;
-				Ok(current_id)
-			})?;
+			ensure!(deposit >= Self::min_order_deposit(), Error::<T>::InvalidDeposit);
+			Self::reserve_deposit(&who, deposit)?;
+			T::MultiCurrency::reserve_named(&RESERVE_ID, currency_id, &who, price.saturating_mul(quantity as Balance))?;
 
-			let info = CategoryData {
-				metadata,
-				nft_count: Default::default(),
+			let offer: ClassOfferData<T> = ClassOfferData {
+				currency_id,
+				price,
+				quantity,
+				deposit,
+				deadline,
 			};
-			Categories::<T>::insert(category_id, info);
+			ClassOffers::<T>::insert(class_id, &who, offer);
 
-			Self::deposit_event(Event::CreatedCategory(category_id));
+			Self::deposit_event(Event::CreatedClassOffer(class_id, who));
 			Ok(().into())
 		}
 
-		/// Update a common category.
+		/// Remove a class offer by offer owner.
 		///
-		/// - `category_id`: category ID
-		/// - `metadata`: metadata
+		/// - `class_id`: class id
 		#[pallet::weight(100_000)]
 		#[transactional]
-		pub fn update_category(origin: OriginFor<T>, category_id: CategoryIdOf<T>, metadata: NFTMetadata) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-			if let Some(category) = Self::categories(category_id) {
-				let info = CategoryData {
-					metadata,
-					nft_count: category.nft_count,
-				};
-				Categories::<T>::insert(category_id, info);
-				Self::deposit_event(Event::UpdatedCategory(category_id));
-			}
+		pub fn remove_class_offer(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::delete_class_offer(class_id, &who)?;
 			Ok(().into())
 		}
 
-		/// Update the `MinOrderDeposit` storage.
+		/// Sell a token to a class offer.
+		///
+		/// - `class_id`: class id
+		/// - `token_id`: the token of `class_id` owned by the caller
+		/// - `price`: The min price to accept. Usually it is set to the price of the target offer.
+		/// - `offer_owner`: the account who made the offer
 		#[pallet::weight(100_000)]
 		#[transactional]
-		pub fn update_min_order_deposit(origin: OriginFor<T>, new_deposit: Balance) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-			MinOrderDeposit::<T>::mutate(|d|{
-				let old = *d;
-				*d = new_deposit;
+		pub fn take_class_offer(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+			#[pallet::compact] price: Balance,
+			offer_owner: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_trader(&who)?;
+			ensure!(offer_owner != who, Error::<T>::TakeOwnClassOffer);
+
+			let offer: ClassOfferData<T> = Self::class_offers(class_id, &offer_owner).ok_or(Error::<T>::ClassOfferNotFound)?;
+			ensure!(<frame_system::Pallet<T>>::block_number() <= offer.deadline, Error::<T>::ClassOfferExpired);
+			ensure!(price <= offer.price, Error::<T>::PriceTooLow);
+
+			let token_owner = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?.owner;
+			ensure!(token_owner == who, Error::<T>::NoPermission);
+
+			// `who` will sell this NFT to `offer_owner`
+			Self::try_delete_order(class_id, token_id, &who, &token_owner);
+			Self::do_transfer(&who, &offer_owner, class_id, token_id)?;
+			let _ = T::MultiCurrency::unreserve_named(&RESERVE_ID, offer.currency_id, &offer_owner, offer.price);
+			T::MultiCurrency::transfer_keep_alive(offer.currency_id, &offer_owner, &who, offer.price)?;
+
+			let remaining = offer.quantity.saturating_sub(One::one());
+			ClassOffers::<T>::mutate(class_id, &offer_owner, |maybe_offer| {
+				maybe_offer.as_mut().map(|o| o.quantity = remaining);
+			});
+			if remaining.is_zero() {
+				Self::delete_class_offer(class_id, &offer_owner)?;
+			}
+
+			Self::deposit_event(Event::TakenClassOffer(class_id, token_id, offer_owner));
+			Ok(().into())
+		}
+
+		/// Create an order selling several tokens at once, possibly across classes.
+		///
+		/// - `currency_id`: currency id
+		/// - `price`: price of the whole bundle
+		/// - `tokens`: the `(class_id, token_id)` pairs to sell
+		/// - `deposit`: The balances to create an order
+		/// - `deadline`: deadline
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn submit_bundle_order(
+			origin: OriginFor<T>,
+			#[pallet::compact] currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] price: Balance,
+			tokens: Vec<(ClassIdOf<T>, TokenIdOf<T>)>,
+			#[pallet::compact] deposit: Balance,
+			#[pallet::compact] deadline: BlockNumberOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_trader(&who)?;
+			Self::ensure_bundle(&who, &tokens)?;
+			T::CurrencyRegistry::ensure_active(currency_id)?;
+			ensure!(<frame_system::Pallet<T>>::block_number() < deadline, Error::<T>::InvalidDeadline);
+
+			ensure!(deposit >= Self::min_order_deposit(), Error::<T>::InvalidDeposit);
+			Self::reserve_deposit(&who, deposit)?;
+
+			let order_id = Self::get_next_order_id()?;
+			let order: BundleOrderData<T> = BundleOrderData {
+				owner: who.clone(),
+				currency_id,
+				price,
+				deposit,
+				deadline,
+				tokens,
+			};
+			BundleOrders::<T>::insert(order_id, order);
+
+			Self::deposit_event(Event::CreatedBundleOrder(order_id, who));
+			Ok(().into())
+		}
+
+		/// Remove a bundle order by order owner.
+		///
+		/// - `order_id`: bundle order id
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn remove_bundle_order(
+			origin: OriginFor<T>,
+			#[pallet::compact] order_id: OrderId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let order: BundleOrderData<T> = Self::bundle_orders(order_id).ok_or(Error::<T>::BundleOrderNotFound)?;
+			ensure!(order.owner == who, Error::<T>::NoPermission);
+			Self::delete_bundle_order(order_id)?;
+			Ok(().into())
+		}
+
+		/// Take a bundle order. All tokens are transferred or none is.
+		///
+		/// - `order_id`: bundle order id
+		/// - `price`: The max price to take the order. Usually it is set to the price of the target order.
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn take_bundle_order(
+			origin: OriginFor<T>,
+			#[pallet::compact] order_id: OrderId,
+			#[pallet::compact] price: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_trader(&who)?;
+			let order: BundleOrderData<T> = Self::bundle_orders(order_id).ok_or(Error::<T>::BundleOrderNotFound)?;
+			ensure!(order.owner != who, Error::<T>::TakeOwnOrder);
+			ensure!(<frame_system::Pallet<T>>::block_number() <= order.deadline, Error::<T>::OrderExpired);
+			ensure!(price >= order.price, Error::<T>::CanNotAfford);
+
+			Self::delete_bundle_order(order_id)?;
+			for &(class_id, token_id) in order.tokens.iter() {
+				Self::try_delete_order(class_id, token_id, &who, &order.owner);
+				// `order.owner` transfers this NFT to `who`, fails if any of them is gone
+				Self::do_transfer(&order.owner, &who, class_id, token_id)?;
+			}
+			T::MultiCurrency::transfer_keep_alive(order.currency_id, &who, &order.owner, order.price)?;
+
+			Self::deposit_event(Event::TakenBundleOrder(order_id, order.owner, who));
+			Ok(().into())
+		}
+
+		/// Offer some tokens, plus an optional top-up, in exchange for another token.
+		///
+		/// - `tokens`: the `(class_id, token_id)` pairs to give away
+		/// - `top_up`: currency id and amount paid on top of `tokens`
+		/// - `target`: a specific token or any token of a class
+		/// - `deposit`: The balances to create an offer
+		/// - `deadline`: deadline
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn submit_swap_offer(
+			origin: OriginFor<T>,
+			tokens: Vec<(ClassIdOf<T>, TokenIdOf<T>)>,
+			top_up: Option<(CurrencyIdOf<T>, Balance)>,
+			target: SwapTarget<ClassIdOf<T>, TokenIdOf<T>>,
+			#[pallet::compact] deposit: Balance,
+			#[pallet::compact] deadline: BlockNumberOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_trader(&who)?;
+			Self::ensure_bundle(&who, &tokens)?;
+			if let Some((currency_id, _)) = top_up {
+				T::CurrencyRegistry::ensure_active(currency_id)?;
+			}
+			match target {
+				SwapTarget::Token(class_id, token_id) => {
+					ensure!(orml_nft::Module::<T>::tokens(class_id, token_id).is_some(), Error::<T>::TokenIdNotFound);
+				},
+				SwapTarget::AnyOf(class_id) => {
+					ensure!(orml_nft::Module::<T>::classes(class_id).is_some(), Error::<T>::ClassIdNotFound);
+				},
+			}
+			ensure!(<frame_system::Pallet<T>>::block_number() < deadline, Error::<T>::InvalidDeadline);
+
+			ensure!(deposit >= Self::min_order_deposit(), Error::<T>::InvalidDeposit);
+			Self::reserve_deposit(&who, deposit)?;
+			if let Some((currency_id, amount)) = top_up {
+				T::MultiCurrency::reserve_named(&RESERVE_ID, currency_id, &who, amount)?;
+			}
+
+			let order_id = Self::get_next_order_id()?;
+			let offer: SwapOfferData<T> = SwapOfferData {
+				owner: who.clone(),
+				tokens,
+				top_up,
+				target,
+				deposit,
+				deadline,
+			};
+			SwapOffers::<T>::insert(order_id, offer);
+
+			Self::deposit_event(Event::CreatedSwapOffer(order_id, who));
+			Ok(().into())
+		}
+
+		/// Remove a swap offer by offer owner.
+		///
+		/// - `order_id`: swap offer id
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn remove_swap_offer(
+			origin: OriginFor<T>,
+			#[pallet::compact] order_id: OrderId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let offer: SwapOfferData<T> = Self::swap_offers(order_id).ok_or(Error::<T>::SwapOfferNotFound)?;
+			ensure!(offer.owner == who, Error::<T>::NoPermission);
+			Self::delete_swap_offer(order_id)?;
+			Ok(().into())
+		}
+
+		/// Accept a swap offer by giving away a token matching its target.
+		/// All transfers happen or none does.
+		///
+		/// - `order_id`: swap offer id
+		/// - `class_id`: class id of the token given to the offer owner
+		/// - `token_id`: token id of the token given to the offer owner
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn take_swap_offer(
+			origin: OriginFor<T>,
+			#[pallet::compact] order_id: OrderId,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] token_id: TokenIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::ensure_trader(&who)?;
+			let offer: SwapOfferData<T> = Self::swap_offers(order_id).ok_or(Error::<T>::SwapOfferNotFound)?;
+			ensure!(offer.owner != who, Error::<T>::TakeOwnOrder);
+			ensure!(<frame_system::Pallet<T>>::block_number() <= offer.deadline, Error::<T>::OrderExpired);
+			ensure!(offer.target.matches(&(class_id, token_id)), Error::<T>::SwapTargetMismatch);
+
+			Self::delete_swap_offer(order_id)?;
+			// `who` may have listed the token it is swapping.
+			Self::try_delete_order(class_id, token_id, &who, &who);
+			Self::do_transfer(&who, &offer.owner, class_id, token_id)?;
+			for &(offered_class_id, offered_token_id) in offer.tokens.iter() {
+				Self::do_transfer(&offer.owner, &who, offered_class_id, offered_token_id)?;
+			}
+			if let Some((currency_id, amount)) = offer.top_up {
+				T::MultiCurrency::transfer_keep_alive(currency_id, &offer.owner, &who, amount)?;
+			}
+
+			Self::deposit_event(Event::TakenSwapOffer(order_id, offer.owner, who));
+			Ok(().into())
+		}
+
+		/// Create a common category for trading NFT.
+		/// A Selling NFT should belong to a category.
+		///
+		/// - `metadata`: metadata
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn create_category(origin: OriginFor<T>, metadata: NFTMetadata) -> DispatchResultWithPostInfo {
+			T::CategoryOrigin::ensure_origin(origin)?;
+			ensure!(metadata.len() <= T::MaxCategoryMetadataLen::get() as usize, Error::<T>::CategoryMetadataTooLong);
+
+			let category_id = NextCategoryId::<T>::try_mutate(|id| -> Result<T::CategoryId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableCategoryId)?;
+				Ok(current_id)
+			})?;
+
+			let info = CategoryData {
+				metadata,
+				nft_count: Default::default(),
+			};
+			Categories::<T>::insert(category_id, info);
+
+			Self::deposit_event(Event::CreatedCategory(category_id));
+			Ok(().into())
+		}
+
+		/// Update a common category.
+		///
+		/// - `category_id`: category ID
+		/// - `metadata`: metadata
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn update_category(origin: OriginFor<T>, category_id: CategoryIdOf<T>, metadata: NFTMetadata) -> DispatchResultWithPostInfo {
+			T::CategoryOrigin::ensure_origin(origin)?;
+			ensure!(metadata.len() <= T::MaxCategoryMetadataLen::get() as usize, Error::<T>::CategoryMetadataTooLong);
+			Categories::<T>::try_mutate(category_id, |maybe_category| -> DispatchResult {
+				let category = maybe_category.as_mut().ok_or(Error::<T>::CategoryNotFound)?;
+				category.metadata = metadata;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::UpdatedCategory(category_id));
+			Ok(().into())
+		}
+
+		/// Update the `MinOrderDeposit` storage.
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn update_min_order_deposit(origin: OriginFor<T>, new_deposit: Balance) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			MinOrderDeposit::<T>::mutate(|d|{
+				let old = *d;
+				*d = new_deposit;
 				Self::deposit_event(Event::UpdatedMinOrderDeposit(old, new_deposit));
 			});
 			Ok(().into())
@@ -598,15 +1445,27 @@ pub mod module {
 		/// - `properties`: class property, include `Transferable` `Burnable`
 		/// - `name`: class name, with len limitation.
 		/// - `description`: class description, with len limitation.
+		/// - `max_supply`: the maximum number of tokens ever minted, unlimited if `None`. It can't be changed later.
 		#[pallet::weight(100_000)]
 		#[transactional]
-		pub fn create_class(origin: OriginFor<T>, metadata: NFTMetadata, name: Vec<u8>, description: Vec<u8>, properties: Properties) -> DispatchResultWithPostInfo {
+		pub fn create_class(
+			origin: OriginFor<T>,
+			metadata: NFTMetadata,
+			name: Vec<u8>,
+			description: Vec<u8>,
+			properties: Properties,
+			max_supply: Option<TokenId>,
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			ensure!(nftmart_config::Pallet::<T>::account_whitelist(&who).is_some(), Error::<T>::AccountNotInWhitelist);
+			ensure!(
+				T::Whitelist::is_whitelisted(&who, WhitelistPermission::CreateClass),
+				Error::<T>::AccountNotInWhitelist
+			);
 
 			// TODO: pass constants from runtime configuration.
 			ensure!(name.len() <= 20, Error::<T>::NameTooLong);
 			ensure!(description.len() <= 256, Error::<T>::DescriptionTooLong);
+			ensure!(max_supply != Some(0), Error::<T>::InvalidQuantity);
 
 			let next_id = orml_nft::Module::<T>::next_class_id();
 			let owner: T::AccountId = T::ModuleId::get().into_sub_account(next_id);
@@ -617,7 +1476,7 @@ pub mod module {
 			);
 
 			<T as Config>::Currency::transfer(&who, &owner, all_deposit.saturated_into(), KeepAlive)?;
-			<T as Config>::Currency::reserve(&owner, deposit.saturated_into())?;
+			Self::reserve_deposit(&owner, deposit)?;
 			// owner add proxy delegate to origin
 			<pallet_proxy::Module<T>>::add_proxy_delegate(&owner, who, Default::default(), Zero::zero())?;
 
@@ -627,6 +1486,7 @@ pub mod module {
 				name,
 				description,
 				create_block: <frame_system::Pallet<T>>::block_number(),
+				max_supply,
 			};
 			orml_nft::Module::<T>::create_class(&owner, metadata, data)?;
 
@@ -651,23 +1511,266 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(to)?;
-			ensure!(nftmart_config::Pallet::<T>::account_whitelist(&to).is_some(), Error::<T>::AccountNotInWhitelist);
+			ensure!(
+				T::Whitelist::is_whitelisted(&to, WhitelistPermission::ReceiveMint),
+				Error::<T>::AccountNotInWhitelist
+			);
 
 			ensure!(quantity >= 1, Error::<T>::InvalidQuantity);
 			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
 			ensure!(who == class_info.owner, Error::<T>::NoPermission);
-			let (deposit, total_deposit) = Self::mint_token_deposit(metadata.len().saturated_into(), quantity);
+			Self::do_mint(&class_info.owner, &to, class_id, metadata, quantity)?;
 
-			<T as Config>::Currency::reserve(&class_info.owner, total_deposit.saturated_into())?;
-			let data: TokenData<BlockNumberOf<T>> = TokenData {
-				deposit,
-				create_block: <frame_system::Pallet<T>>::block_number(),
-			};
-			for _ in 0..quantity {
-				orml_nft::Module::<T>::mint(&to, class_id, metadata.clone(), data.clone())?;
+			Self::deposit_event(Event::MintedToken(who, to, class_id, quantity));
+			Ok(().into())
+		}
+
+		/// Set the primary sale of a class, replacing the previous phases.
+		///
+		/// Tokens already claimed in a phase still count towards its per-account cap.
+		///
+		/// - `class_id`: class id
+		/// - `metadata`: external metadata of the tokens sold
+		/// - `phases`: phases of the sale
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn set_primary_sale(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			metadata: NFTMetadata,
+			phases: Vec<SalePhase<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			ensure!(!phases.is_empty() && phases.len() <= T::MaxSalePhases::get() as usize, Error::<T>::InvalidSalePhases);
+			for phase in &phases {
+				ensure!(phase.start < phase.end && phase.per_account_cap >= 1, Error::<T>::InvalidSalePhases);
+				if let Some(SaleAllowlist::Accounts(accounts)) = &phase.allowlist {
+					ensure!(accounts.len() <= T::MaxSaleAllowlistLen::get() as usize, Error::<T>::InvalidSalePhases);
+				}
+				T::CurrencyRegistry::ensure_active(phase.currency_id)?;
 			}
 
-			Self::deposit_event(Event::MintedToken(who, to, class_id, quantity));
+			PrimarySales::<T>::insert(class_id, PrimarySaleData { metadata, phases });
+			Self::deposit_event(Event::SetPrimarySale(class_id));
+			Ok(().into())
+		}
+
+		/// Remove the primary sale of a class.
+		///
+		/// - `class_id`: class id
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn remove_primary_sale(origin: OriginFor<T>, #[pallet::compact] class_id: ClassIdOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(PrimarySales::<T>::contains_key(class_id), Error::<T>::PrimarySaleNotFound);
+			Self::delete_primary_sale(class_id);
+			Self::deposit_event(Event::RemovedPrimarySale(class_id));
+			Ok(().into())
+		}
+
+		/// Claim tokens in a phase of the primary sale of a class, paying the class owner.
+		///
+		/// - `class_id`: class id
+		/// - `phase_index`: index of the phase
+		/// - `quantity`: token quantity, at most `MaxClaimQuantity`
+		/// - `proof`: Merkle proof of the caller, if the phase has a Merkle root, at most `MaxSaleProofLen` long
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn claim_mint(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] phase_index: u32,
+			#[pallet::compact] quantity: u32,
+			proof: Vec<H256>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				T::Whitelist::is_whitelisted(&who, WhitelistPermission::ReceiveMint),
+				Error::<T>::AccountNotInWhitelist
+			);
+			ensure!(quantity >= 1 && quantity <= T::MaxClaimQuantity::get(), Error::<T>::InvalidQuantity);
+			ensure!(proof.len() <= T::MaxSaleProofLen::get() as usize, Error::<T>::SaleProofTooLong);
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			let sale = Self::primary_sales(class_id).ok_or(Error::<T>::PrimarySaleNotFound)?;
+			let phase = sale.phases.get(phase_index as usize).ok_or(Error::<T>::SalePhaseNotFound)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(phase.start <= now && now < phase.end, Error::<T>::SalePhaseNotActive);
+			if let Some(allowlist) = &phase.allowlist {
+				ensure!(allowlist.allows(&who, &proof), Error::<T>::NotInSaleAllowlist);
+			}
+
+			SaleClaims::<T>::try_mutate(class_id, (phase_index, &who), |claimed| -> DispatchResult {
+				*claimed = claimed.checked_add(quantity).filter(|c| *c <= phase.per_account_cap).ok_or(Error::<T>::SaleCapExceeded)?;
+				Ok(())
+			})?;
+
+			let amount = phase.price.checked_mul(quantity as Balance).ok_or(Error::<T>::CanNotAfford)?;
+			T::MultiCurrency::transfer_keep_alive(phase.currency_id, &who, &class_info.owner, amount)?;
+			Self::do_mint(&class_info.owner, &who, class_id, sale.metadata, quantity)?;
+
+			Self::deposit_event(Event::ClaimedMint(who, class_id, phase_index, quantity));
+			Ok(().into())
+		}
+
+		/// Create the blind box of a class, items are added by `add_blind_box_items`.
+		///
+		/// - `class_id`: class id
+		/// - `currency_id`: currency id
+		/// - `price`: price of each box
+		/// - `placeholder`: metadata of the sold tokens until they are revealed
+		/// - `reveal_at`: sold tokens are revealed from this block number, or as soon as their seed is recorded if `None`
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn create_blind_box(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] price: Balance,
+			placeholder: NFTMetadata,
+			reveal_at: Option<BlockNumberOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(!BlindBoxes::<T>::contains_key(class_id), Error::<T>::BlindBoxExists);
+			ensure!(placeholder.len() <= T::MaxBlindBoxItemLen::get() as usize, Error::<T>::BlindBoxItemTooLong);
+			T::CurrencyRegistry::ensure_active(currency_id)?;
+
+			BlindBoxes::<T>::insert(class_id, BlindBoxData {
+				currency_id,
+				price,
+				placeholder,
+				reveal_at,
+				items: Vec::new(),
+				unrevealed: Vec::new(),
+			});
+			Self::deposit_event(Event::CreatedBlindBox(class_id));
+			Ok(().into())
+		}
+
+		/// Add items to the pool of a blind box.
+		///
+		/// - `class_id`: class id
+		/// - `items`: metadata of the items
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn add_blind_box_items(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			items: Vec<NFTMetadata>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(
+				items.iter().all(|item| item.len() <= T::MaxBlindBoxItemLen::get() as usize),
+				Error::<T>::BlindBoxItemTooLong
+			);
+			let count = items.len() as u32;
+			BlindBoxes::<T>::try_mutate(class_id, |maybe_blind_box| -> DispatchResult {
+				let blind_box = maybe_blind_box.as_mut().ok_or(Error::<T>::BlindBoxNotFound)?;
+				blind_box.items.extend(items);
+				ensure!(blind_box.items.len() <= T::MaxBlindBoxItems::get() as usize, Error::<T>::TooManyBlindBoxItems);
+				Ok(())
+			})?;
+			Self::deposit_event(Event::AddedBlindBoxItems(class_id, count));
+			Ok(().into())
+		}
+
+		/// Remove the blind box of a class, with the items not drawn yet.
+		///
+		/// - `class_id`: class id
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn remove_blind_box(origin: OriginFor<T>, #[pallet::compact] class_id: ClassIdOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			let blind_box = Self::blind_boxes(class_id).ok_or(Error::<T>::BlindBoxNotFound)?;
+			ensure!(blind_box.unrevealed.is_empty(), Error::<T>::UnrevealedTokensExist);
+			BlindBoxes::<T>::remove(class_id);
+			Self::deposit_event(Event::RemovedBlindBox(class_id));
+			Ok(().into())
+		}
+
+		/// Buy blind boxes of a class, paying the class owner.
+		///
+		/// Each box is a token with the placeholder metadata until it is revealed by `reveal`.
+		///
+		/// - `class_id`: class id
+		/// - `quantity`: the number of boxes
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn buy_blind_box(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+			#[pallet::compact] quantity: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				T::Whitelist::is_whitelisted(&who, WhitelistPermission::ReceiveMint),
+				Error::<T>::AccountNotInWhitelist
+			);
+			ensure!(quantity >= 1, Error::<T>::InvalidQuantity);
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+
+			BlindBoxes::<T>::try_mutate(class_id, |maybe_blind_box| -> DispatchResult {
+				let blind_box = maybe_blind_box.as_mut().ok_or(Error::<T>::BlindBoxNotFound)?;
+				// items are kept for the sold tokens waiting for their reveal.
+				let available = blind_box.items.len().saturating_sub(blind_box.unrevealed.len());
+				ensure!(quantity as usize <= available, Error::<T>::BlindBoxSoldOut);
+
+				let amount = blind_box.price.checked_mul(quantity as Balance).ok_or(Error::<T>::CanNotAfford)?;
+				T::MultiCurrency::transfer_keep_alive(blind_box.currency_id, &who, &class_info.owner, amount)?;
+
+				// the items are drawn from the seed of the current round, recorded when the round ends.
+				let (round, _) = Self::randomness_round();
+				for _ in 0..quantity {
+					let token_id = orml_nft::Module::<T>::next_token_id(class_id);
+					Self::do_mint(&class_info.owner, &who, class_id, blind_box.placeholder.clone(), 1)?;
+					blind_box.unrevealed.push((token_id, round));
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::BoughtBlindBox(who, class_id, quantity));
+			Ok(().into())
+		}
+
+		/// Reveal the next token sold in a blind box, drawing its item at random from the pool.
+		///
+		/// Tokens are revealed in the order of purchase, each with the seed of the round it was bought in,
+		/// so that neither the buyer nor the caller can choose the drawn item.
+		///
+		/// - `class_id`: class id
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn reveal(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: ClassIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+
+			let (token_id, item) = BlindBoxes::<T>::try_mutate(class_id, |maybe_blind_box| -> Result<_, DispatchError> {
+				let blind_box = maybe_blind_box.as_mut().ok_or(Error::<T>::BlindBoxNotFound)?;
+				let &(token_id, round) = blind_box.unrevealed.first().ok_or(Error::<T>::NothingToReveal)?;
+				let now = <frame_system::Pallet<T>>::block_number();
+				ensure!(blind_box.reveal_at.map_or(true, |reveal_at| reveal_at <= now), Error::<T>::RevealNotReady);
+				let seed = Self::round_seeds(round).ok_or(Error::<T>::RevealNotReady)?;
+				blind_box.unrevealed.remove(0);
+				let item = Self::draw_item(class_id, token_id, seed, blind_box).ok_or(Error::<T>::BlindBoxSoldOut)?;
+				Ok((token_id, item))
+			})?;
+			Self::set_token_metadata(&class_info.owner, class_id, token_id, item)?;
+
+			Self::deposit_event(Event::RevealedToken(class_id, token_id));
 			Ok(().into())
 		}
 
@@ -709,11 +1812,16 @@ pub mod module {
 
 			ensure!(Self::orders((class_id, token_id), &who).is_none(), Error::<T>::OrderExists);
 			orml_nft::Module::<T>::burn(&who, (class_id, token_id))?;
+			BlindBoxes::<T>::mutate(class_id, |maybe_blind_box| {
+				if let Some(blind_box) = maybe_blind_box {
+					blind_box.unrevealed.retain(|(unrevealed, _)| *unrevealed != token_id);
+				}
+			});
 			let owner: T::AccountId = T::ModuleId::get().into_sub_account(class_id);
 			let data = token_info.data;
 			// `repatriate_reserved` will check `to` account exist and return `DeadAccount`.
 			// `transfer` not do this check.
-			<T as Config>::Currency::unreserve(&owner, data.deposit.saturated_into());
+			Self::unreserve_deposit(&owner, data.deposit);
 			<T as Config>::Currency::transfer(&owner, &who, data.deposit.saturated_into(), KeepAlive)?;
 
 			Self::deposit_event(Event::BurnedToken(who, class_id, token_id));
@@ -744,12 +1852,14 @@ pub mod module {
 			let data = class_info.data;
 			// `repatriate_reserved` will check `to` account exist and return `DeadAccount`.
 			// `transfer` not do this check.
-			<T as Config>::Currency::unreserve(&owner, data.deposit.saturated_into());
+			Self::unreserve_deposit(&owner, data.deposit);
 			// At least there is one admin at this point.
 			<T as Config>::Currency::transfer(&owner, &dest, data.deposit.saturated_into(), KeepAlive)?;
 
 			// transfer all free from origin to dest
 			orml_nft::Module::<T>::destroy_class(&who, class_id)?;
+			Self::delete_primary_sale(class_id);
+			BlindBoxes::<T>::remove(class_id);
 
 			Self::deposit_event(Event::DestroyedClass(who, class_id, dest));
 			Ok(().into())
@@ -759,6 +1869,21 @@ pub mod module {
 
 impl<T: Config> Pallet<T> {
 
+	/// Reserve a deposit of the native currency under `RESERVE_ID`.
+	fn reserve_deposit(who: &T::AccountId, deposit: Balance) -> DispatchResult {
+		T::MultiCurrency::reserve_named(&RESERVE_ID, T::GetNativeCurrencyId::get(), who, deposit)
+	}
+
+	/// Release a deposit reserved by `reserve_deposit`, returning the amount unreserved.
+	fn unreserve_deposit(who: &T::AccountId, deposit: Balance) -> Balance {
+		deposit.saturating_sub(T::MultiCurrency::unreserve_named(&RESERVE_ID, T::GetNativeCurrencyId::get(), who, deposit))
+	}
+
+	fn ensure_trader(who: &T::AccountId) -> DispatchResult {
+		ensure!(T::Whitelist::can_trade(who), Error::<T>::AccountNotInWhitelist);
+		Ok(())
+	}
+
 	fn is_burnable(class_id: ClassIdOf<T>) -> Result<bool, DispatchError> {
 		let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
 		let data = class_info.data;
@@ -768,11 +1893,11 @@ impl<T: Config> Pallet<T> {
 	fn delete_order(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, who: &T::AccountId, token_owner: &T::AccountId) -> DispatchResult {
 		Orders::<T>::try_mutate_exists((class_id, token_id), who, |maybe_order| {
 			let order = maybe_order.as_mut().ok_or(Error::<T>::OrderNotFound)?;
-			let deposit = <T as Config>::Currency::unreserve(&who, order.deposit.saturated_into());
-			Self::deposit_event(Event::RemovedOrder(class_id, token_id, who.clone(), deposit.saturated_into()));
+			let deposit = Self::unreserve_deposit(&who, order.deposit);
+			Self::deposit_event(Event::RemovedOrder(class_id, token_id, who.clone(), deposit));
 
 			if who != token_owner {
-				let _ = T::MultiCurrency::unreserve(order.currency_id, &who, order.price.saturated_into());
+				let _ = T::MultiCurrency::unreserve_named(&RESERVE_ID, order.currency_id, &who, order.price);
 			}
 
 			Categories::<T>::try_mutate(order.category_id, |category| -> DispatchResult {
@@ -781,10 +1906,105 @@ impl<T: Config> Pallet<T> {
 			})?;
 
 			*maybe_order = None;
+			ClassOrders::<T>::remove(class_id, (token_id, who.clone()));
 			Ok(())
 		})
 	}
 
+	fn delete_class_offer(class_id: ClassIdOf<T>, who: &T::AccountId) -> DispatchResult {
+		let offer = ClassOffers::<T>::take(class_id, who).ok_or(Error::<T>::ClassOfferNotFound)?;
+		let deposit = Self::unreserve_deposit(&who, offer.deposit);
+		let _ = T::MultiCurrency::unreserve_named(&RESERVE_ID, offer.currency_id, &who, offer.price.saturating_mul(offer.quantity as Balance));
+		Self::deposit_event(Event::RemovedClassOffer(class_id, who.clone(), deposit));
+		Ok(())
+	}
+
+	fn delete_bundle_order(order_id: OrderId) -> DispatchResult {
+		let order = BundleOrders::<T>::take(order_id).ok_or(Error::<T>::BundleOrderNotFound)?;
+		let deposit = Self::unreserve_deposit(&order.owner, order.deposit);
+		Self::deposit_event(Event::RemovedBundleOrder(order_id, order.owner, deposit));
+		Ok(())
+	}
+
+	fn delete_swap_offer(order_id: OrderId) -> DispatchResult {
+		let offer = SwapOffers::<T>::take(order_id).ok_or(Error::<T>::SwapOfferNotFound)?;
+		let deposit = Self::unreserve_deposit(&offer.owner, offer.deposit);
+		if let Some((currency_id, amount)) = offer.top_up {
+			let _ = T::MultiCurrency::unreserve_named(&RESERVE_ID, currency_id, &offer.owner, amount);
+		}
+		Self::deposit_event(Event::RemovedSwapOffer(order_id, offer.owner, deposit));
+		Ok(())
+	}
+
+	/// Ensure `tokens` is a non-empty set of at most `MaxBundleSize` tokens owned by `who`.
+	fn ensure_bundle(who: &T::AccountId, tokens: &[(ClassIdOf<T>, TokenIdOf<T>)]) -> DispatchResult {
+		ensure!(!tokens.is_empty() && tokens.len() <= T::MaxBundleSize::get() as usize, Error::<T>::InvalidBundle);
+		for (i, &(class_id, token_id)) in tokens.iter().enumerate() {
+			ensure!(!tokens[..i].contains(&(class_id, token_id)), Error::<T>::InvalidBundle);
+			let token: orml_nft::TokenInfoOf<T> = orml_nft::Module::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?;
+			ensure!(token.owner == *who, Error::<T>::NoPermission);
+		}
+		Ok(())
+	}
+
+	/// Reserve the token deposits from `owner`, the account of `class_id`, and mint `quantity` tokens to `to`.
+	fn do_mint(owner: &T::AccountId, to: &T::AccountId, class_id: ClassIdOf<T>, metadata: NFTMetadata, quantity: u32) -> DispatchResult {
+		ensure!(
+			Self::remaining_supply(class_id).map_or(true, |remaining| quantity as TokenId <= remaining),
+			Error::<T>::MaxSupplyExceeded
+		);
+		let (deposit, total_deposit) = Self::mint_token_deposit(metadata.len().saturated_into(), quantity);
+		Self::reserve_deposit(owner, total_deposit)?;
+		let data: TokenData<BlockNumberOf<T>> = TokenData {
+			deposit,
+			create_block: <frame_system::Pallet<T>>::block_number(),
+		};
+		for _ in 0..quantity {
+			orml_nft::Module::<T>::mint(to, class_id, metadata.clone(), data.clone())?;
+		}
+		Ok(())
+	}
+
+	/// Draw the item of `token_id` from the pool of `blind_box` with `seed`, removing it from the pool.
+	fn draw_item(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, seed: T::Hash, blind_box: &mut BlindBoxData<T>) -> Option<NFTMetadata> {
+		if blind_box.items.is_empty() {
+			return None;
+		}
+		let random = BlakeTwo256::hash_of(&(seed, class_id, token_id));
+		let random = u32::decode(&mut random.as_ref()).unwrap_or_default();
+		let index = random as usize % blind_box.items.len();
+		Some(blind_box.items.swap_remove(index))
+	}
+
+	/// Replace the metadata of a token, adjusting its deposit reserved from `owner`, the account of `class_id`.
+	fn set_token_metadata(owner: &T::AccountId, class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, metadata: NFTMetadata) -> DispatchResult {
+		orml_nft::Tokens::<T>::try_mutate(class_id, token_id, |maybe_token| -> DispatchResult {
+			let token = maybe_token.as_mut().ok_or(Error::<T>::TokenIdNotFound)?;
+			let (deposit, _) = Self::mint_token_deposit(metadata.len().saturated_into(), 1);
+			if deposit > token.data.deposit {
+				Self::reserve_deposit(owner, deposit - token.data.deposit)?;
+			} else {
+				Self::unreserve_deposit(owner, token.data.deposit - deposit);
+			}
+			token.data.deposit = deposit;
+			token.metadata = metadata;
+			Ok(())
+		})
+	}
+
+	fn delete_primary_sale(class_id: ClassIdOf<T>) {
+		PrimarySales::<T>::remove(class_id);
+		SaleClaims::<T>::remove_prefix(class_id);
+	}
+
+	fn get_next_order_id() -> Result<OrderId, DispatchError> {
+		NextOrderId::<T>::try_mutate(|id| -> Result<OrderId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(One::one()).ok_or(Error::<T>::NoAvailableOrderId)?;
+			Ok(current_id)
+		})
+	}
+
 	fn try_delete_order(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, who: &T::AccountId, token_owner: &T::AccountId) {
 		let _ = Self::delete_order(class_id, token_id, who, token_owner);
 	}
@@ -810,6 +2030,39 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Currency and price of the order of `owner`, `None` if missing or expired.
+	pub fn order_price(class_id: ClassIdOf<T>, token_id: TokenIdOf<T>, owner: &T::AccountId) -> Option<(CurrencyIdOf<T>, Balance)> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		Self::orders((class_id, token_id), owner)
+			.filter(|order| now <= order.deadline)
+			.map(|order| (order.currency_id, order.price))
+	}
+
+	/// Currencies and prices of the unexpired asks on the tokens of `class_id`.
+	///
+	/// Orders made by accounts not owning the token are bids and are not counted.
+	pub fn class_order_prices(class_id: ClassIdOf<T>) -> Vec<(CurrencyIdOf<T>, Balance)> {
+		ClassOrders::<T>::iter_prefix(class_id)
+			.filter(|((token_id, who), _)| orml_nft::Module::<T>::is_owner(who, (class_id, *token_id)))
+			.filter_map(|((token_id, who), _)| Self::order_price(class_id, token_id, &who))
+			.collect()
+	}
+
+	/// The number of tokens which can still be minted in `class_id`.
+	///
+	/// `None` if the class does not exist or has no max supply.
+	pub fn remaining_supply(class_id: ClassIdOf<T>) -> Option<TokenId> {
+		let max_supply = orml_nft::Module::<T>::classes(class_id)?.data.max_supply?;
+		// Burned tokens still count, so the next token ID is the number of tokens ever minted.
+		let minted: TokenId = orml_nft::Module::<T>::next_token_id(class_id).saturated_into();
+		Some(max_supply.saturating_sub(minted))
+	}
+
+	/// Whether `call` belongs to a paused group of calls.
+	pub fn is_paused(call: &Call<T>) -> bool {
+		call.call_group().map_or(false, T::PausedCalls::is_paused)
+	}
+
 	pub fn add_class_admin_deposit(admin_count: u32) -> Balance {
 		let proxy_deposit_before: Balance = <pallet_proxy::Module<T>>::deposit(1).saturated_into();
 		let proxy_deposit_after: Balance = <pallet_proxy::Module<T>>::deposit(admin_count.saturating_add(1)).saturated_into();
@@ -819,8 +2072,8 @@ impl<T: Config> Pallet<T> {
 	pub fn mint_token_deposit(metadata_len: u32, quantity: u32) -> (Balance, Balance) {
 		let deposit: Balance = {
 			let total_bytes = metadata_len;
-			T::CreateTokenDeposit::get().saturating_add(
-				(total_bytes as Balance).saturating_mul(T::MetaDataByteDeposit::get())
+			T::MarketParameters::get(MarketParameter::CreateTokenDeposit).saturating_add(
+				(total_bytes as Balance).saturating_mul(T::MarketParameters::get(MarketParameter::MetaDataByteDeposit))
 			)
 		};
 		let total_deposit: Balance = deposit.saturating_mul(quantity as Balance);
@@ -830,11 +2083,130 @@ impl<T: Config> Pallet<T> {
 	pub fn create_class_deposit(metadata_len: u32, name_len: u32, description_len: u32) -> (Balance, Balance) {
 		let deposit: Balance = {
 			let total_bytes = metadata_len.saturating_add(name_len).saturating_add(description_len);
-			T::CreateClassDeposit::get().saturating_add(
-				(total_bytes as Balance).saturating_mul(T::MetaDataByteDeposit::get())
+			T::MarketParameters::get(MarketParameter::CreateClassDeposit).saturating_add(
+				(total_bytes as Balance).saturating_mul(T::MarketParameters::get(MarketParameter::MetaDataByteDeposit))
 			)
 		};
 		let proxy_deposit: Balance = <pallet_proxy::Module<T>>::deposit(1).saturated_into();
 		(deposit, deposit.saturating_add(proxy_deposit))
 	}
 }
+
+impl<T: Config> NFT<T::AccountId> for Pallet<T> {
+	type ClassId = ClassIdOf<T>;
+	type TokenId = TokenIdOf<T>;
+	type Balance = TokenIdOf<T>;
+
+	fn balance(who: &T::AccountId) -> Self::Balance {
+		orml_nft::Module::<T>::token_balances(who)
+	}
+
+	fn owner(token: (Self::ClassId, Self::TokenId)) -> Option<T::AccountId> {
+		orml_nft::Module::<T>::tokens(token.0, token.1).map(|info| info.owner)
+	}
+
+	/// Transfer like the `transfer` call, failing if the transfers are paused.
+	fn transfer(from: &T::AccountId, to: &T::AccountId, token: (Self::ClassId, Self::TokenId)) -> DispatchResult {
+		ensure!(!T::PausedCalls::is_paused(CallGroup::Transfers), Error::<T>::CallsPaused);
+		Self::do_transfer(from, to, token.0, token.1)
+	}
+
+	fn class_owner(class_id: Self::ClassId) -> Option<T::AccountId> {
+		orml_nft::Module::<T>::classes(class_id).map(|info| info.owner)
+	}
+
+	/// Mint a token like the `mint` call, failing if the minting is paused.
+	fn mint(
+		class_owner: &T::AccountId,
+		to: &T::AccountId,
+		class_id: Self::ClassId,
+		metadata: NFTMetadata,
+	) -> Result<Self::TokenId, DispatchError> {
+		ensure!(!T::PausedCalls::is_paused(CallGroup::Minting), Error::<T>::CallsPaused);
+		ensure!(
+			T::Whitelist::is_whitelisted(to, WhitelistPermission::ReceiveMint),
+			Error::<T>::AccountNotInWhitelist
+		);
+		let class_info = orml_nft::Module::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		ensure!(*class_owner == class_info.owner, Error::<T>::NoPermission);
+		let token_id = orml_nft::Module::<T>::next_token_id(class_id);
+		Self::do_mint(class_owner, to, class_id, metadata, 1)?;
+		Self::deposit_event(Event::MintedToken(class_owner.clone(), to.clone(), class_id, 1));
+		Ok(token_id)
+	}
+}
+
+impl<T: Config> Call<T> {
+	/// The group of calls this call is paused with, if any.
+	///
+	/// Removing orders and offers is never paused, so that users can always get their reserves back.
+	pub fn call_group(&self) -> Option<CallGroup> {
+		match self {
+			Call::take_order(..) |
+			Call::submit_order(..) |
+			Call::update_order_price(..) |
+			Call::submit_class_offer(..) |
+			Call::take_class_offer(..) |
+			Call::submit_bundle_order(..) |
+			Call::take_bundle_order(..) |
+			Call::submit_swap_offer(..) |
+			Call::take_swap_offer(..) => Some(CallGroup::Trading),
+			Call::mint(..) |
+			Call::claim_mint(..) |
+			Call::buy_blind_box(..) => Some(CallGroup::Minting),
+			Call::create_class(..) => Some(CallGroup::ClassCreation),
+			Call::transfer(..) => Some(CallGroup::Transfers),
+			_ => None,
+		}
+	}
+}
+
+/// Rejects the calls of the paused groups when validating transactions.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default)]
+pub struct CheckPaused<T: Config + Send + Sync>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckPaused<T> {
+	/// Creates new `SignedExtension` to check the paused calls.
+	pub fn new() -> Self {
+		Self(sp_std::marker::PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckPaused<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckPaused")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckPaused<T> where
+	<T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckPaused";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		_who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		match call.is_sub_type() {
+			Some(call) if Pallet::<T>::is_paused(call) => InvalidTransaction::Call.into(),
+			_ => Ok(ValidTransaction::default()),
+		}
+	}
+}
@@ -131,6 +131,11 @@ orml_traits::parameter_type_with_key! {
 	};
 }
 
+parameter_types! {
+	pub const ExemptModuleAccounts: bool = true;
+	pub const MaxReserves: u32 = 50;
+}
+
 impl orml_tokens::Config for Runtime {
 	type Event = Event;
 	type Balance = Balance;
@@ -139,6 +144,9 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = ();
+	type ExemptModuleAccounts = ExemptModuleAccounts;
+	type ReserveIdentifier = [u8; 8];
+	type MaxReserves = MaxReserves;
 }
 
 parameter_types! {
@@ -152,6 +160,8 @@ impl orml_currencies::Config for Runtime {
 	type MultiCurrency = Tokens;
 	type NativeCurrency = AdaptedBasicCurrency;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type IssuerOrigin = frame_system::EnsureRoot<AccountId>;
+	type IssuableCurrencies = CurrencyRegistry;
 	type WeightInfo = ();
 }
 
@@ -163,25 +173,104 @@ impl orml_nft::Config for Runtime {
 }
 
 parameter_types! {
-	pub const CreateClassDeposit: Balance = 50;
-	pub const CreateTokenDeposit: Balance = 10;
-	pub const MetaDataByteDeposit: Balance = 1;
 	pub const NftModuleId: ModuleId = ModuleId(*b"nftmart*");
+	pub const MaxCategoryMetadataLen: u32 = 64;
+	pub const MaxBundleSize: u32 = 10;
+	pub const MaxTakers: u32 = 2;
+	pub const MaxSalePhases: u32 = 4;
+	pub const MaxSaleAllowlistLen: u32 = 3;
+	pub const MaxClaimQuantity: u32 = 2;
+	pub const MaxSaleProofLen: u32 = 2;
+	pub const MaxBlindBoxItems: u32 = 4;
+	pub const MaxBlindBoxItemLen: u32 = 8;
+}
+
+/// Randomness changing every 10 blocks and derived from the block number, so that the draws are reproducible.
+pub struct TestRandomness;
+impl frame_support::traits::Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let known_since = System::block_number() / 10 * 10;
+		(BlakeTwo256::hash_of(&(subject, known_since)), known_since)
+	}
 }
 
 impl nftmart_nft::Config for Runtime {
 	type Event = Event;
-	type CreateClassDeposit = CreateClassDeposit;
-	type MetaDataByteDeposit = MetaDataByteDeposit;
-	type CreateTokenDeposit = CreateTokenDeposit;
 	type ModuleId = NftModuleId;
 	type Currency = Balances;
 	type MultiCurrency = Currencies;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type CategoryId = sp_core::constants_types::CategoryId;
+	type CategoryOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxCategoryMetadataLen = MaxCategoryMetadataLen;
+	type MaxBundleSize = MaxBundleSize;
+	type MaxTakers = MaxTakers;
+	type MaxSalePhases = MaxSalePhases;
+	type MaxSaleAllowlistLen = MaxSaleAllowlistLen;
+	type MaxClaimQuantity = MaxClaimQuantity;
+	type MaxSaleProofLen = MaxSaleProofLen;
+	type Randomness = TestRandomness;
+	type MaxBlindBoxItems = MaxBlindBoxItems;
+	type MaxBlindBoxItemLen = MaxBlindBoxItemLen;
+	type CurrencyRegistry = CurrencyRegistry;
+	type Whitelist = NftmartConfig;
+	type MarketParameters = NftmartConfig;
+	type PausedCalls = NftmartConfig;
+}
+
+parameter_types! {
+	pub const NativeDecimals: u8 = 12;
+	pub const StringLimit: u32 = 32;
+}
+
+impl nftmart_currency_registry::Config for Runtime {
+	type Event = Event;
+	type CurrencyId = CurrencyId;
+	type Balance = Balance;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type NativeDecimals = NativeDecimals;
+	type RegistryOrigin = frame_system::EnsureRoot<AccountId>;
+	type StringLimit = StringLimit;
+	type InitialCurrencies = ();
+}
+
+parameter_types! {
+	pub const ApplicationDeposit: Balance = 10;
+	pub const MaxApplicationInfoLen: u32 = 64;
+}
+
+orml_traits::parameter_type_with_key! {
+	pub DefaultParameters: |parameter: nftmart_config::MarketParameter| -> Balance {
+		match parameter {
+			nftmart_config::MarketParameter::CreateClassDeposit => 50,
+			nftmart_config::MarketParameter::CreateTokenDeposit => 10,
+			nftmart_config::MarketParameter::MetaDataByteDeposit => 1,
+		}
+	};
+}
+
+orml_traits::parameter_type_with_key! {
+	pub ParameterBounds: |parameter: nftmart_config::MarketParameter| -> (Balance, Balance) {
+		match parameter {
+			nftmart_config::MarketParameter::MetaDataByteDeposit => (1, 10),
+			_ => (1, 100),
+		}
+	};
 }
 
 impl nftmart_config::Config for Runtime {
 	type Event = Event;
+	type WhitelistOrigin = frame_system::EnsureRoot<AccountId>;
+	type IdentityJudgements = ();
+	type Currency = Balances;
+	type ApplicationDeposit = ApplicationDeposit;
+	type MaxApplicationInfoLen = MaxApplicationInfoLen;
+	type ApproveOrigin = frame_system::EnsureRoot<AccountId>;
+	type Slashed = ();
+	type ParameterOrigin = frame_system::EnsureRoot<AccountId>;
+	type PauseOrigin = frame_system::EnsureRoot<AccountId>;
+	type DefaultParameters = DefaultParameters;
+	type ParameterBounds = ParameterBounds;
 }
 
 use frame_system::Call as SystemCall;
@@ -200,21 +289,25 @@ construct_runtime!(
 		Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
 		Utility: pallet_utility::{Module, Call, Event},
 		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
-		Currencies: orml_currencies::{Module, Call, Event<T>},
+		Currencies: orml_currencies::{Module, Call, Storage, Event<T>},
 		OrmlNFT: orml_nft::{Module, Storage, Config<T>},
 		NftmartConfig: nftmart_config::{Module, Call, Event<T>},
+		CurrencyRegistry: nftmart_currency_registry::{Module, Call, Storage, Event<T>, Config<T>},
 		Nftmart: nftmart_nft::{Module, Call, Event<T>},
 	}
 );
 
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
 pub const BOB: AccountId = AccountId::new([2u8; 32]);
+pub const CHARLIE: AccountId = AccountId::new([3u8; 32]);
 pub const CLASS_ID: <Runtime as orml_nft::Config>::ClassId = 0;
 pub const CATEGORY_ID: <Runtime as Config>::CategoryId = 0;
 pub const CATEGORY_ID_NOT_EXIST: <Runtime as Config>::CategoryId = 100;
 pub const CLASS_ID_NOT_EXIST: <Runtime as orml_nft::Config>::ClassId = 1;
 pub const TOKEN_ID: <Runtime as orml_nft::Config>::TokenId = 0;
 pub const TOKEN_ID_NOT_EXIST: <Runtime as orml_nft::Config>::TokenId = 1;
+pub const CURRENCY_ID: CurrencyId = 1;
+pub const CURRENCY_ID_NOT_EXIST: CurrencyId = 100;
 
 pub struct ExtBuilder;
 impl Default for ExtBuilder {
@@ -235,11 +328,17 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		nftmart_currency_registry::GenesisConfig::<Runtime> {
+			currencies: vec![(CURRENCY_ID, b"Token".to_vec(), b"TKN".to_vec(), 12, 0)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| {
 			System::set_block_number(1);
-			NftmartConfig::add_whitelist(Origin::root(), ALICE);
-			NftmartConfig::add_whitelist(Origin::root(), BOB);
+			let _ = NftmartConfig::add_whitelist(Origin::root(), ALICE, nftmart_config::Permissions::all(), None);
+			let _ = NftmartConfig::add_whitelist(Origin::root(), BOB, nftmart_config::Permissions::all(), None);
 		});
 		ext
 	}
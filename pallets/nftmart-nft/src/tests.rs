@@ -1,8 +1,9 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
-use mock::{Event, *};
+use frame_support::{assert_noop, assert_ok, StorageHasher};
+use mock::{CurrencyRegistry, Event, *};
+use sp_core::constants_types::NATIVE_CURRENCY_ID;
 
 fn free_balance(who: &AccountId) -> Balance {
 	<Runtime as Config>::Currency::free_balance(who)
@@ -31,7 +32,11 @@ fn create_category_should_work() {
 		assert_eq!(Some(CategoryData{ metadata, nft_count: 0 }), Nftmart::categories(CATEGORY_ID));
 		assert_eq!(None, Nftmart::categories(CATEGORY_ID_NOT_EXIST));
 
-		// TODO: test update
+		let metadata = vec![2];
+		assert_ok!(Nftmart::update_category(Origin::root(), CATEGORY_ID, metadata.clone()));
+		let event = Event::nftmart_nft(crate::Event::UpdatedCategory(CATEGORY_ID));
+		assert_eq!(last_event(), event);
+		assert_eq!(Some(CategoryData{ metadata, nft_count: 0 }), Nftmart::categories(CATEGORY_ID));
 	});
 }
 
@@ -51,6 +56,33 @@ fn create_category_should_fail() {
 			Error::<Runtime>::NoAvailableCategoryId,
 		);
 	});
+	ExtBuilder::default().build().execute_with(|| {
+		let metadata = vec![1; MaxCategoryMetadataLen::get() as usize + 1];
+		assert_noop!(
+			Nftmart::create_category(Origin::root(), metadata),
+			Error::<Runtime>::CategoryMetadataTooLong,
+		);
+	});
+}
+
+#[test]
+fn update_category_should_fail() {
+	let metadata = vec![1];
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_category(Origin::root(), metadata.clone()));
+		assert_noop!(
+			Nftmart::update_category(Origin::signed(ALICE), CATEGORY_ID, metadata.clone()),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			Nftmart::update_category(Origin::root(), CATEGORY_ID_NOT_EXIST, metadata.clone()),
+			Error::<Runtime>::CategoryNotFound,
+		);
+		assert_noop!(
+			Nftmart::update_category(Origin::root(), CATEGORY_ID, vec![1; MaxCategoryMetadataLen::get() as usize + 1]),
+			Error::<Runtime>::CategoryMetadataTooLong,
+		);
+	});
 }
 
 #[test]
@@ -59,7 +91,7 @@ fn create_class_should_work() {
 		let metadata = vec![1];
 		let name = vec![1];
 		let description = vec![1];
-		assert_ok!(Nftmart::create_class(Origin::signed(ALICE), metadata.clone(), name.clone(), description.clone(), Default::default()));
+		assert_ok!(Nftmart::create_class(Origin::signed(ALICE), metadata.clone(), name.clone(), description.clone(), Default::default(), None));
 
 		let event = Event::nftmart_nft(crate::Event::CreatedClass(class_id_account(), CLASS_ID));
 		assert_eq!(last_event(), event);
@@ -76,10 +108,46 @@ fn create_class_should_fail() {
 			Nftmart::create_class(
 				Origin::signed(BOB),
 				vec![1], vec![1], vec![1],
-				Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
 			),
 			pallet_balances::Error::<Runtime, _>::InsufficientBalance
 		);
+
+		assert_ok!(NftmartConfig::add_whitelist(
+			Origin::root(), CHARLIE, nftmart_config::Permissions(WhitelistPermission::ReceiveMint.into()), None,
+		));
+		assert_noop!(
+			Nftmart::create_class(
+				Origin::signed(CHARLIE),
+				vec![1], vec![1], vec![1],
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+			),
+			Error::<Runtime>::AccountNotInWhitelist
+		);
+	});
+}
+
+#[test]
+fn deposits_should_follow_market_parameters() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Nftmart::create_class_deposit(2, 1, 1).0, 50 + 4);
+		assert_eq!(Nftmart::mint_token_deposit(2, 3), (10 + 2, 36));
+
+		assert_ok!(NftmartConfig::set_parameter(Origin::root(), MarketParameter::CreateClassDeposit, 30));
+		assert_ok!(NftmartConfig::set_parameter(Origin::root(), MarketParameter::CreateTokenDeposit, 5));
+		assert_ok!(NftmartConfig::set_parameter(Origin::root(), MarketParameter::MetaDataByteDeposit, 2));
+		assert_eq!(Nftmart::create_class_deposit(2, 1, 1).0, 30 + 8);
+		assert_eq!(Nftmart::mint_token_deposit(2, 3), (5 + 4, 27));
+
+		let free = free_balance(&ALICE);
+		let all_deposit = Nftmart::create_class_deposit(1, 1, 1).1;
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			vec![1], vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(free_balance(&ALICE), free - all_deposit);
+		assert_eq!(reserved_balance(&class_id_account()), all_deposit);
 	});
 }
 
@@ -93,7 +161,7 @@ fn mint_should_work() {
 			assert_ok!(Nftmart::create_class(
 				Origin::signed(ALICE),
 				metadata.clone(), name.clone(), description.clone(),
-				Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
 			));
 			let event = Event::nftmart_nft(crate::Event::CreatedClass(class_id_account(), CLASS_ID));
 			assert_eq!(last_event(), event);
@@ -130,7 +198,7 @@ fn mint_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), vec![1], vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
 		));
 		assert_noop!(
 			Nftmart::mint(Origin::signed(ALICE), BOB, CLASS_ID_NOT_EXIST, vec![1], 2),
@@ -147,6 +215,14 @@ fn mint_should_fail() {
 			Error::<Runtime>::NoPermission
 		);
 
+		assert_ok!(NftmartConfig::add_whitelist(
+			Origin::root(), CHARLIE, nftmart_config::Permissions(WhitelistPermission::CreateClass.into()), None,
+		));
+		assert_noop!(
+			Nftmart::mint(Origin::signed(class_id_account()), CHARLIE, CLASS_ID, vec![1], 2),
+			Error::<Runtime>::AccountNotInWhitelist
+		);
+
 		orml_nft::NextTokenId::<Runtime>::mutate(CLASS_ID, |id| {
 			*id = <Runtime as orml_nft::Config>::TokenId::max_value()
 		});
@@ -168,7 +244,7 @@ fn transfer_should_work() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), vec![1], vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
 		));
 		let deposit = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
@@ -198,7 +274,7 @@ fn transfer_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), vec![1], vec![1],
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -226,7 +302,7 @@ fn transfer_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), vec![1], vec![1],
-			Default::default()
+			Default::default(), None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -254,7 +330,7 @@ fn burn_should_work() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata, name, description,
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -289,7 +365,7 @@ fn burn_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), name.clone(), description.clone(),
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -322,7 +398,7 @@ fn burn_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata.clone(), name.clone(), description.clone(),
-			Default::default()
+			Default::default(), None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -354,7 +430,7 @@ fn destroy_class_should_work() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata, name, description,
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
 		));
 		assert_eq!(free_balance(&ALICE), 100000 - deposit_class);
 		assert_eq!(free_balance(&class_id_account()), 0);
@@ -399,7 +475,7 @@ fn destroy_class_should_fail() {
 		assert_ok!(Nftmart::create_class(
 			Origin::signed(ALICE),
 			metadata, name, description,
-			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
 		));
 		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
 		assert_ok!(Nftmart::mint(
@@ -432,3 +508,967 @@ fn destroy_class_should_fail() {
 		));
 	});
 }
+
+#[test]
+fn class_offer_should_work() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 2));
+
+		let reserved = reserved_balance(&ALICE);
+		assert_ok!(Nftmart::submit_class_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CLASS_ID, 2, 1, 10));
+		let event = Event::nftmart_nft(crate::Event::CreatedClassOffer(CLASS_ID, ALICE));
+		assert_eq!(last_event(), event);
+		assert_eq!(reserved_balance(&ALICE), reserved + 21);
+
+		assert_ok!(Nftmart::take_class_offer(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 10, ALICE));
+		let event = Event::nftmart_nft(crate::Event::TakenClassOffer(CLASS_ID, TOKEN_ID, ALICE));
+		assert_eq!(last_event(), event);
+		assert_eq!(reserved_balance(&ALICE), reserved + 11);
+		assert_eq!(free_balance(&BOB), 10);
+		assert_eq!(Nftmart::class_offers(CLASS_ID, ALICE).map(|o| o.quantity), Some(1));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+
+		assert_ok!(Nftmart::take_class_offer(Origin::signed(BOB), CLASS_ID, TOKEN_ID + 1, 10, ALICE));
+		let event = Event::nftmart_nft(crate::Event::TakenClassOffer(CLASS_ID, TOKEN_ID + 1, ALICE));
+		assert_eq!(last_event(), event);
+		assert_eq!(reserved_balance(&ALICE), reserved);
+		assert_eq!(free_balance(&BOB), 20);
+		assert_eq!(Nftmart::class_offers(CLASS_ID, ALICE), None);
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID + 1)));
+	});
+}
+
+#[test]
+fn class_offer_should_fail() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 1).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 1));
+
+		assert_noop!(
+			Nftmart::submit_class_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CLASS_ID_NOT_EXIST, 1, 1, 10),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			Nftmart::submit_class_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CLASS_ID, 0, 1, 10),
+			Error::<Runtime>::InvalidQuantity
+		);
+		assert_noop!(
+			Nftmart::submit_class_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CLASS_ID, 1, 1, 1),
+			Error::<Runtime>::InvalidDeadline
+		);
+		assert_ok!(Nftmart::submit_class_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CLASS_ID, 1, 1, 10));
+		assert_noop!(
+			Nftmart::submit_class_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CLASS_ID, 1, 1, 10),
+			Error::<Runtime>::DuplicatedClassOffer
+		);
+		assert_noop!(
+			Nftmart::take_class_offer(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 10, ALICE),
+			Error::<Runtime>::TakeOwnClassOffer
+		);
+		assert_noop!(
+			Nftmart::take_class_offer(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 11, ALICE),
+			Error::<Runtime>::PriceTooLow
+		);
+		assert_noop!(
+			Nftmart::remove_class_offer(Origin::signed(BOB), CLASS_ID),
+			Error::<Runtime>::ClassOfferNotFound
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Nftmart::take_class_offer(Origin::signed(BOB), CLASS_ID, TOKEN_ID, 10, ALICE),
+			Error::<Runtime>::ClassOfferExpired
+		);
+
+		let reserved = reserved_balance(&ALICE);
+		assert_ok!(Nftmart::remove_class_offer(Origin::signed(ALICE), CLASS_ID));
+		assert_eq!(reserved_balance(&ALICE), reserved - 11);
+		assert_eq!(Nftmart::class_offers(CLASS_ID, ALICE), None);
+	});
+}
+
+#[test]
+fn bundle_order_should_work() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 2));
+
+		let tokens = vec![(CLASS_ID, TOKEN_ID), (CLASS_ID, TOKEN_ID + 1)];
+		assert_ok!(Nftmart::submit_bundle_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, tokens.clone(), 0, 10));
+		let event = Event::nftmart_nft(crate::Event::CreatedBundleOrder(0, BOB));
+		assert_eq!(last_event(), event);
+		assert_eq!(Nftmart::bundle_orders(0).map(|o| o.tokens), Some(tokens));
+		assert_eq!(Nftmart::next_order_id(), 1);
+
+		let free = free_balance(&ALICE);
+		assert_ok!(Nftmart::take_bundle_order(Origin::signed(ALICE), 0, 10));
+		let event = Event::nftmart_nft(crate::Event::TakenBundleOrder(0, BOB, ALICE));
+		assert_eq!(last_event(), event);
+		assert_eq!(free_balance(&ALICE), free - 10);
+		assert_eq!(free_balance(&BOB), 10);
+		assert_eq!(Nftmart::bundle_orders(0), None);
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID + 1)));
+	});
+}
+
+#[test]
+fn take_bundle_order_should_keep_buyer_alive() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 2));
+
+		let tokens = vec![(CLASS_ID, TOKEN_ID), (CLASS_ID, TOKEN_ID + 1)];
+		assert_ok!(Nftmart::submit_bundle_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, tokens, 0, 10));
+
+		let _ = Balances::deposit_creating(&CHARLIE, 10);
+		assert_noop!(
+			Nftmart::take_bundle_order(Origin::signed(CHARLIE), 0, 10),
+			pallet_balances::Error::<Runtime, _>::KeepAlive
+		);
+
+		let _ = Balances::deposit_creating(&CHARLIE, 1);
+		assert_ok!(Nftmart::take_bundle_order(Origin::signed(CHARLIE), 0, 10));
+		assert_eq!(free_balance(&CHARLIE), 1);
+		assert_eq!(free_balance(&BOB), 10);
+	});
+}
+
+#[test]
+fn bundle_order_should_fail() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 2));
+
+		assert_noop!(
+			Nftmart::submit_bundle_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, vec![], 0, 10),
+			Error::<Runtime>::InvalidBundle
+		);
+		assert_noop!(
+			Nftmart::submit_bundle_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, vec![(CLASS_ID, TOKEN_ID), (CLASS_ID, TOKEN_ID)], 0, 10),
+			Error::<Runtime>::InvalidBundle
+		);
+		assert_noop!(
+			Nftmart::submit_bundle_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, vec![(CLASS_ID, TOKEN_ID)], 0, 10),
+			Error::<Runtime>::NoPermission
+		);
+
+		let tokens = vec![(CLASS_ID, TOKEN_ID), (CLASS_ID, TOKEN_ID + 1)];
+		assert_ok!(Nftmart::submit_bundle_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, tokens, 0, 10));
+		assert_noop!(
+			Nftmart::take_bundle_order(Origin::signed(BOB), 0, 10),
+			Error::<Runtime>::TakeOwnOrder
+		);
+		assert_noop!(
+			Nftmart::take_bundle_order(Origin::signed(ALICE), 0, 9),
+			Error::<Runtime>::CanNotAfford
+		);
+		assert_noop!(
+			Nftmart::remove_bundle_order(Origin::signed(ALICE), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		// One token left the bundle, nothing is transferred.
+		assert_ok!(Nftmart::transfer(Origin::signed(BOB), ALICE, CLASS_ID, TOKEN_ID + 1));
+		assert_noop!(
+			Nftmart::take_bundle_order(Origin::signed(ALICE), 0, 10),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(Nftmart::remove_bundle_order(Origin::signed(BOB), 0));
+		assert_eq!(Nftmart::bundle_orders(0), None);
+	});
+}
+
+#[test]
+fn swap_offer_should_work() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 1));
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), ALICE, CLASS_ID, vec![1], 1));
+
+		let reserved = reserved_balance(&ALICE);
+		assert_ok!(Nftmart::submit_swap_offer(
+			Origin::signed(ALICE),
+			vec![(CLASS_ID, TOKEN_ID + 1)],
+			Some((NATIVE_CURRENCY_ID, 5)),
+			SwapTarget::AnyOf(CLASS_ID),
+			1, 10
+		));
+		let event = Event::nftmart_nft(crate::Event::CreatedSwapOffer(0, ALICE));
+		assert_eq!(last_event(), event);
+		assert_eq!(reserved_balance(&ALICE), reserved + 6);
+
+		assert_ok!(Nftmart::take_swap_offer(Origin::signed(BOB), 0, CLASS_ID, TOKEN_ID));
+		let event = Event::nftmart_nft(crate::Event::TakenSwapOffer(0, ALICE, BOB));
+		assert_eq!(last_event(), event);
+		assert_eq!(reserved_balance(&ALICE), reserved);
+		assert_eq!(free_balance(&BOB), 5);
+		assert_eq!(Nftmart::swap_offers(0), None);
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&BOB, (CLASS_ID, TOKEN_ID + 1)));
+	});
+}
+
+#[test]
+fn take_swap_offer_should_remove_the_order_of_the_taker() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 1));
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), ALICE, CLASS_ID, vec![1], 1));
+
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![]));
+		assert_ok!(Nftmart::submit_swap_offer(
+			Origin::signed(ALICE),
+			vec![(CLASS_ID, TOKEN_ID + 1)],
+			None,
+			SwapTarget::AnyOf(CLASS_ID),
+			1, 10
+		));
+
+		assert_ok!(Nftmart::take_swap_offer(Origin::signed(BOB), 0, CLASS_ID, TOKEN_ID));
+		assert_eq!(Nftmart::orders((CLASS_ID, TOKEN_ID), BOB), None);
+		assert_eq!(Nftmart::class_order_prices(CLASS_ID), vec![]);
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&BOB, (CLASS_ID, TOKEN_ID + 1)));
+	});
+}
+
+#[test]
+fn swap_offer_should_fail() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 3).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 1));
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), ALICE, CLASS_ID, vec![1], 1));
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 1));
+
+		assert_noop!(
+			Nftmart::submit_swap_offer(Origin::signed(ALICE), vec![(CLASS_ID, TOKEN_ID)], None, SwapTarget::AnyOf(CLASS_ID), 1, 10),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			Nftmart::submit_swap_offer(Origin::signed(ALICE), vec![(CLASS_ID, TOKEN_ID + 1)], None, SwapTarget::Token(CLASS_ID, 100), 1, 10),
+			Error::<Runtime>::TokenIdNotFound
+		);
+		assert_noop!(
+			Nftmart::submit_swap_offer(Origin::signed(ALICE), vec![(CLASS_ID, TOKEN_ID + 1)], None, SwapTarget::AnyOf(CLASS_ID_NOT_EXIST), 1, 10),
+			Error::<Runtime>::ClassIdNotFound
+		);
+
+		assert_ok!(Nftmart::submit_swap_offer(
+			Origin::signed(ALICE), vec![(CLASS_ID, TOKEN_ID + 1)], None, SwapTarget::Token(CLASS_ID, TOKEN_ID), 1, 10
+		));
+		assert_noop!(
+			Nftmart::take_swap_offer(Origin::signed(BOB), 1, CLASS_ID, TOKEN_ID),
+			Error::<Runtime>::SwapOfferNotFound
+		);
+		assert_noop!(
+			Nftmart::take_swap_offer(Origin::signed(ALICE), 0, CLASS_ID, TOKEN_ID),
+			Error::<Runtime>::TakeOwnOrder
+		);
+		assert_noop!(
+			Nftmart::take_swap_offer(Origin::signed(BOB), 0, CLASS_ID, TOKEN_ID + 2),
+			Error::<Runtime>::SwapTargetMismatch
+		);
+		assert_noop!(
+			Nftmart::remove_swap_offer(Origin::signed(BOB), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(Nftmart::remove_swap_offer(Origin::signed(ALICE), 0));
+		assert_eq!(Nftmart::swap_offers(0), None);
+	});
+}
+
+#[test]
+fn reserved_order_should_work() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 1).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 1));
+
+		assert_noop!(
+			Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![ALICE, BOB, CHARLIE]),
+			Error::<Runtime>::TooManyTakers
+		);
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![CHARLIE]));
+		assert_eq!(Nftmart::orders((CLASS_ID, TOKEN_ID), BOB).map(|o| o.takers), Some(vec![CHARLIE]));
+		assert_noop!(
+			Nftmart::take_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 10, BOB),
+			Error::<Runtime>::NotOrderTaker
+		);
+
+		assert_ok!(Nftmart::remove_order(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![ALICE, CHARLIE]));
+		assert_ok!(Nftmart::take_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID, 10, BOB));
+		let event = Event::nftmart_nft(crate::Event::TakenOrder(CLASS_ID, TOKEN_ID, BOB));
+		assert_eq!(last_event(), event);
+		assert_eq!(free_balance(&BOB), 10);
+		assert!(orml_nft::Module::<Runtime>::is_owner(&ALICE, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn trade_restriction_should_work() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 1).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable.into()), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 1));
+		let _ = Balances::deposit_creating(&CHARLIE, 100);
+
+		// anyone trades unless restricted.
+		assert_ok!(Nftmart::submit_order(Origin::signed(CHARLIE), NATIVE_CURRENCY_ID, 5, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![]));
+		assert_ok!(Nftmart::remove_order(Origin::signed(CHARLIE), CLASS_ID, TOKEN_ID));
+
+		assert_ok!(NftmartConfig::set_trade_restricted(Origin::root(), true));
+		assert_noop!(
+			Nftmart::submit_order(Origin::signed(CHARLIE), NATIVE_CURRENCY_ID, 5, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![]),
+			Error::<Runtime>::AccountNotInWhitelist
+		);
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![]));
+		assert_noop!(
+			Nftmart::take_order(Origin::signed(CHARLIE), CLASS_ID, TOKEN_ID, 10, BOB),
+			Error::<Runtime>::AccountNotInWhitelist
+		);
+
+		assert_ok!(NftmartConfig::add_whitelist(
+			Origin::root(), CHARLIE, nftmart_config::Permissions(WhitelistPermission::Trade.into()), None,
+		));
+		assert_ok!(Nftmart::take_order(Origin::signed(CHARLIE), CLASS_ID, TOKEN_ID, 10, BOB));
+		assert!(orml_nft::Module::<Runtime>::is_owner(&CHARLIE, (CLASS_ID, TOKEN_ID)));
+	});
+}
+
+#[test]
+fn submit_order_should_check_currency() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 1).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 1));
+
+		assert_noop!(
+			Nftmart::submit_order(Origin::signed(BOB), CURRENCY_ID_NOT_EXIST, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![]),
+			nftmart_currency_registry::Error::<Runtime>::CurrencyNotRegistered
+		);
+		assert_noop!(
+			Nftmart::submit_class_offer(Origin::signed(ALICE), CURRENCY_ID_NOT_EXIST, 10, CLASS_ID, 1, 0, 10),
+			nftmart_currency_registry::Error::<Runtime>::CurrencyNotRegistered
+		);
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![]));
+		assert_ok!(Nftmart::remove_order(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+
+		assert_ok!(CurrencyRegistry::deprecate_currency(Origin::root(), CURRENCY_ID));
+		assert_noop!(
+			Nftmart::submit_order(Origin::signed(BOB), CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![]),
+			nftmart_currency_registry::Error::<Runtime>::CurrencyDeprecated
+		);
+		assert_noop!(
+			Nftmart::submit_bundle_order(Origin::signed(BOB), CURRENCY_ID, 10, vec![(CLASS_ID, TOKEN_ID)], 0, 10),
+			nftmart_currency_registry::Error::<Runtime>::CurrencyDeprecated
+		);
+	});
+}
+
+#[test]
+fn order_prices_should_work() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 2).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable.into()), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 2));
+
+		assert_eq!(Nftmart::order_price(CLASS_ID, TOKEN_ID, &BOB), None);
+		assert_eq!(Nftmart::class_order_prices(CLASS_ID), vec![]);
+
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![]));
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), CURRENCY_ID, 20, CATEGORY_ID, CLASS_ID, TOKEN_ID + 1, 0, 20, vec![]));
+		assert_eq!(Nftmart::order_price(CLASS_ID, TOKEN_ID, &BOB), Some((NATIVE_CURRENCY_ID, 10)));
+		let mut prices = Nftmart::class_order_prices(CLASS_ID);
+		prices.sort();
+		assert_eq!(prices, vec![(NATIVE_CURRENCY_ID, 10), (CURRENCY_ID, 20)]);
+		assert_eq!(Nftmart::class_order_prices(CLASS_ID_NOT_EXIST), vec![]);
+
+		// bids are not counted.
+		assert_ok!(Nftmart::submit_order(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 5, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 20, vec![]));
+		assert_eq!(Nftmart::order_price(CLASS_ID, TOKEN_ID, &ALICE), Some((NATIVE_CURRENCY_ID, 5)));
+		let mut prices = Nftmart::class_order_prices(CLASS_ID);
+		prices.sort();
+		assert_eq!(prices, vec![(NATIVE_CURRENCY_ID, 10), (CURRENCY_ID, 20)]);
+
+		System::set_block_number(11);
+		assert_eq!(Nftmart::order_price(CLASS_ID, TOKEN_ID, &BOB), None);
+		assert_eq!(Nftmart::class_order_prices(CLASS_ID), vec![(CURRENCY_ID, 20)]);
+
+		assert_ok!(Nftmart::remove_order(Origin::signed(ALICE), CLASS_ID, TOKEN_ID));
+		assert_ok!(Nftmart::remove_order(Origin::signed(BOB), CLASS_ID, TOKEN_ID + 1));
+		assert_eq!(ClassOrders::<Runtime>::iter_prefix(CLASS_ID).map(|(key, _)| key).collect::<Vec<_>>(), vec![(TOKEN_ID, BOB)]);
+		assert_eq!(Nftmart::class_order_prices(CLASS_ID), vec![]);
+	});
+}
+
+#[test]
+fn migrate_class_orders_should_work() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 1).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 1));
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![]));
+
+		ClassOrders::<Runtime>::remove_all();
+		assert_eq!(Nftmart::class_order_prices(CLASS_ID), vec![]);
+		migrations::migrate_class_orders::<Runtime>();
+		assert_eq!(Nftmart::class_order_prices(CLASS_ID), vec![(NATIVE_CURRENCY_ID, 10)]);
+	});
+}
+
+#[test]
+fn deposits_should_use_named_reserves() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 1).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		let (deposit_class, _) = Nftmart::create_class_deposit(metadata.len() as u32, 1, 1);
+		assert_eq!(Currencies::reserved_balance_named(&RESERVE_ID, NATIVE_CURRENCY_ID, &class_id_account()), deposit_class);
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 1));
+		assert_eq!(
+			Currencies::reserved_balance_named(&RESERVE_ID, NATIVE_CURRENCY_ID, &class_id_account()),
+			deposit_class + deposit_token,
+		);
+
+		// a reserve made by another pallet is never released by the marketplace.
+		assert_ok!(Currencies::reserve(NATIVE_CURRENCY_ID, &ALICE, 5));
+		let reserved = reserved_balance(&ALICE);
+		assert_ok!(Nftmart::submit_class_offer(Origin::signed(ALICE), NATIVE_CURRENCY_ID, 10, CLASS_ID, 2, 1, 10));
+		assert_eq!(Currencies::reserved_balance_named(&RESERVE_ID, NATIVE_CURRENCY_ID, &ALICE), 21);
+		assert_eq!(reserved_balance(&ALICE), reserved + 21);
+
+		assert_ok!(Nftmart::remove_class_offer(Origin::signed(ALICE), CLASS_ID));
+		let event = Event::nftmart_nft(crate::Event::RemovedClassOffer(CLASS_ID, ALICE, 1));
+		assert_eq!(last_event(), event);
+		assert_eq!(Currencies::reserved_balance_named(&RESERVE_ID, NATIVE_CURRENCY_ID, &ALICE), 0);
+		assert_eq!(reserved_balance(&ALICE), reserved);
+		assert_eq!(Currencies::unreserve_named(&RESERVE_ID, NATIVE_CURRENCY_ID, &ALICE, 5), 5);
+		assert_eq!(reserved_balance(&ALICE), reserved);
+	});
+}
+
+#[test]
+fn paused_calls_should_be_rejected() {
+	ExtBuilder::default().build().execute_with(|| {
+		let info = Default::default();
+		let transfer = crate::Call::<Runtime>::transfer(BOB, CLASS_ID, TOKEN_ID);
+		let remove_order = crate::Call::<Runtime>::remove_order(CLASS_ID, TOKEN_ID);
+		assert_eq!(transfer.call_group(), Some(CallGroup::Transfers));
+		assert_eq!(remove_order.call_group(), None);
+		let transfer = mock::Call::Nftmart(transfer);
+		let remove_order = mock::Call::Nftmart(remove_order);
+		assert_ok!(CheckPaused::<Runtime>::new().validate(&ALICE, &transfer, &info, 0));
+
+		assert_ok!(NftmartConfig::pause(Origin::root(), CallGroup::Transfers));
+		assert_eq!(
+			CheckPaused::<Runtime>::new().validate(&ALICE, &transfer, &info, 0),
+			InvalidTransaction::Call.into(),
+		);
+		assert_ok!(CheckPaused::<Runtime>::new().validate(&ALICE, &remove_order, &info, 0));
+
+		assert_ok!(NftmartConfig::unpause(Origin::root(), CallGroup::Transfers));
+		assert_ok!(CheckPaused::<Runtime>::new().validate(&ALICE, &transfer, &info, 0));
+	});
+}
+
+#[test]
+fn claim_mint_should_work() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 4).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		let _ = Balances::deposit_creating(&BOB, 100);
+		let _ = Balances::deposit_creating(&CHARLIE, 100);
+
+		let phase = |start, end, price, allowlist| SalePhase::<Runtime> {
+			start, end, currency_id: NATIVE_CURRENCY_ID, price, per_account_cap: 2, allowlist,
+		};
+		let phases = vec![
+			phase(1, 5, 10, Some(SaleAllowlist::Accounts(vec![BOB]))),
+			phase(5, 10, 20, None),
+		];
+		assert_noop!(
+			Nftmart::set_primary_sale(Origin::signed(ALICE), CLASS_ID, metadata.clone(), phases.clone()),
+			Error::<Runtime>::NoPermission,
+		);
+		assert_ok!(Nftmart::set_primary_sale(Origin::signed(class_id_account()), CLASS_ID, metadata.clone(), phases));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::SetPrimarySale(CLASS_ID)));
+
+		let class_free = free_balance(&class_id_account());
+		assert_noop!(
+			Nftmart::claim_mint(Origin::signed(CHARLIE), CLASS_ID, 0, 1, vec![]),
+			Error::<Runtime>::AccountNotInWhitelist,
+		);
+		assert_ok!(NftmartConfig::add_whitelist(
+			Origin::root(), CHARLIE, nftmart_config::Permissions(WhitelistPermission::ReceiveMint.into()), None,
+		));
+		assert_noop!(
+			Nftmart::claim_mint(Origin::signed(CHARLIE), CLASS_ID, 0, 1, vec![]),
+			Error::<Runtime>::NotInSaleAllowlist,
+		);
+		assert_noop!(
+			Nftmart::claim_mint(Origin::signed(BOB), CLASS_ID, 1, 1, vec![]),
+			Error::<Runtime>::SalePhaseNotActive,
+		);
+		assert_noop!(
+			Nftmart::claim_mint(Origin::signed(BOB), CLASS_ID, 0, 3, vec![]),
+			Error::<Runtime>::InvalidQuantity,
+		);
+		assert_noop!(
+			Nftmart::claim_mint(Origin::signed(BOB), CLASS_ID, 0, 1, vec![Default::default(); 3]),
+			Error::<Runtime>::SaleProofTooLong,
+		);
+		assert_ok!(Nftmart::claim_mint(Origin::signed(BOB), CLASS_ID, 0, 2, vec![]));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::ClaimedMint(BOB, CLASS_ID, 0, 2)));
+		assert_eq!(Nftmart::sale_claims(CLASS_ID, (0, BOB)), 2);
+		assert_eq!(free_balance(&BOB), 80);
+		assert_eq!(free_balance(&class_id_account()), class_free + 20 - deposit_token / 2);
+		assert!(orml_nft::Module::<Runtime>::is_owner(&BOB, (CLASS_ID, TOKEN_ID + 1)));
+		assert_noop!(
+			Nftmart::claim_mint(Origin::signed(BOB), CLASS_ID, 0, 1, vec![]),
+			Error::<Runtime>::SaleCapExceeded,
+		);
+
+		System::set_block_number(5);
+		assert_ok!(Nftmart::claim_mint(Origin::signed(CHARLIE), CLASS_ID, 1, 1, vec![]));
+		assert_eq!(free_balance(&CHARLIE), 80);
+		assert!(orml_nft::Module::<Runtime>::is_owner(&CHARLIE, (CLASS_ID, TOKEN_ID + 2)));
+
+		System::set_block_number(10);
+		assert_noop!(
+			Nftmart::claim_mint(Origin::signed(CHARLIE), CLASS_ID, 1, 1, vec![]),
+			Error::<Runtime>::SalePhaseNotActive,
+		);
+
+		assert_ok!(Nftmart::remove_primary_sale(Origin::signed(class_id_account()), CLASS_ID));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RemovedPrimarySale(CLASS_ID)));
+		assert_eq!(Nftmart::primary_sales(CLASS_ID), None);
+		assert_eq!(Nftmart::sale_claims(CLASS_ID, (0, BOB)), 0);
+		assert_noop!(
+			Nftmart::claim_mint(Origin::signed(BOB), CLASS_ID, 0, 1, vec![]),
+			Error::<Runtime>::PrimarySaleNotFound,
+		);
+	});
+}
+
+#[test]
+fn sale_allowlist_should_verify_merkle_proofs() {
+	let leaf_alice = BlakeTwo256::hash_of(&ALICE);
+	let leaf_charlie = BlakeTwo256::hash_of(&CHARLIE);
+	let root = if leaf_alice <= leaf_charlie {
+		BlakeTwo256::hash_of(&(leaf_alice, leaf_charlie))
+	} else {
+		BlakeTwo256::hash_of(&(leaf_charlie, leaf_alice))
+	};
+	let allowlist = SaleAllowlist::<AccountId>::MerkleRoot(root);
+	assert!(allowlist.allows(&ALICE, &[leaf_charlie]));
+	assert!(allowlist.allows(&CHARLIE, &[leaf_alice]));
+	assert!(!allowlist.allows(&BOB, &[leaf_alice]));
+	assert!(!allowlist.allows(&ALICE, &[]));
+}
+
+#[test]
+fn max_supply_should_work() {
+	let metadata = vec![1];
+	let deposit_token = Nftmart::mint_token_deposit(metadata.len() as u32, 3).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Nftmart::create_class(
+				Origin::signed(ALICE),
+				metadata.clone(), vec![1], vec![1],
+				Properties(ClassProperty::Transferable | ClassProperty::Burnable), Some(0)
+			),
+			Error::<Runtime>::InvalidQuantity,
+		);
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			metadata.clone(), vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), Some(2)
+		));
+		assert_eq!(Nftmart::remaining_supply(CLASS_ID), Some(2));
+		assert_eq!(Nftmart::remaining_supply(CLASS_ID_NOT_EXIST), None);
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+
+		assert_noop!(
+			Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 3),
+			Error::<Runtime>::MaxSupplyExceeded,
+		);
+		assert_ok!(Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 2));
+		assert_eq!(Nftmart::remaining_supply(CLASS_ID), Some(0));
+
+		// burned tokens still count towards the max supply.
+		assert_ok!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_eq!(Nftmart::remaining_supply(CLASS_ID), Some(0));
+		assert_noop!(
+			Nftmart::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, metadata.clone(), 1),
+			Error::<Runtime>::MaxSupplyExceeded,
+		);
+
+		let phases = vec![SalePhase::<Runtime> {
+			start: 1, end: 10, currency_id: NATIVE_CURRENCY_ID, price: 0, per_account_cap: 1, allowlist: None,
+		}];
+		assert_ok!(Nftmart::set_primary_sale(Origin::signed(class_id_account()), CLASS_ID, metadata.clone(), phases));
+		assert_noop!(
+			Nftmart::claim_mint(Origin::signed(BOB), CLASS_ID, 0, 1, vec![]),
+			Error::<Runtime>::MaxSupplyExceeded,
+		);
+	});
+}
+
+#[test]
+fn migrate_max_supply_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let data = migrations::ClassDataV4 {
+			deposit: 10,
+			properties: Properties(ClassProperty::Transferable.into()),
+			name: vec![1],
+			description: vec![2],
+			create_block: 3u64,
+		};
+		let info = orml_nft::ClassInfo { metadata: vec![4], total_issuance: 5u64, owner: ALICE, data };
+		frame_support::storage::migration::put_storage_value(
+			b"OrmlNFT", b"Classes", &Twox64Concat::hash(&CLASS_ID.encode()), info);
+
+		migrations::migrate_max_supply::<Runtime>();
+		let info = orml_nft::Module::<Runtime>::classes(CLASS_ID).unwrap();
+		assert_eq!(info.data, ClassData {
+			deposit: 10,
+			properties: Properties(ClassProperty::Transferable.into()),
+			name: vec![1],
+			description: vec![2],
+			create_block: 3,
+			max_supply: None,
+		});
+		assert_eq!((info.metadata, info.total_issuance, info.owner), (vec![4], 5, ALICE));
+	});
+}
+
+fn create_blind_box(reveal_at: Option<BlockNumberOf<Runtime>>) {
+	assert_ok!(Nftmart::create_class(
+		Origin::signed(ALICE),
+		vec![1], vec![1], vec![1],
+		Properties(ClassProperty::Transferable | ClassProperty::Burnable), None
+	));
+	let deposit_token = Nftmart::mint_token_deposit(1, 4).1;
+	assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+	let _ = Balances::deposit_creating(&BOB, 100);
+
+	assert_noop!(
+		Nftmart::create_blind_box(Origin::signed(ALICE), CLASS_ID, NATIVE_CURRENCY_ID, 10, vec![], reveal_at),
+		Error::<Runtime>::NoPermission,
+	);
+	assert_noop!(
+		Nftmart::create_blind_box(Origin::signed(class_id_account()), CLASS_ID, NATIVE_CURRENCY_ID, 10, vec![0; 9], reveal_at),
+		Error::<Runtime>::BlindBoxItemTooLong,
+	);
+	assert_ok!(Nftmart::create_blind_box(Origin::signed(class_id_account()), CLASS_ID, NATIVE_CURRENCY_ID, 10, vec![], reveal_at));
+	assert_eq!(last_event(), Event::nftmart_nft(crate::Event::CreatedBlindBox(CLASS_ID)));
+	assert_noop!(
+		Nftmart::create_blind_box(Origin::signed(class_id_account()), CLASS_ID, NATIVE_CURRENCY_ID, 10, vec![], reveal_at),
+		Error::<Runtime>::BlindBoxExists,
+	);
+
+	assert_noop!(
+		Nftmart::add_blind_box_items(Origin::signed(class_id_account()), CLASS_ID, vec![vec![1], vec![0; 9]]),
+		Error::<Runtime>::BlindBoxItemTooLong,
+	);
+	assert_ok!(Nftmart::add_blind_box_items(Origin::signed(class_id_account()), CLASS_ID, vec![vec![1], vec![2], vec![3]]));
+	assert_eq!(last_event(), Event::nftmart_nft(crate::Event::AddedBlindBoxItems(CLASS_ID, 3)));
+	assert_noop!(
+		Nftmart::add_blind_box_items(Origin::signed(class_id_account()), CLASS_ID, vec![vec![4], vec![5]]),
+		Error::<Runtime>::TooManyBlindBoxItems,
+	);
+}
+
+fn token_metadata(token_id: TokenIdOf<Runtime>) -> NFTMetadata {
+	orml_nft::Module::<Runtime>::tokens(CLASS_ID, token_id).unwrap().metadata
+}
+
+fn unrevealed_tokens() -> Vec<(TokenIdOf<Runtime>, u32)> {
+	Nftmart::blind_boxes(CLASS_ID).unwrap().unrevealed
+}
+
+fn run_to_block(n: BlockNumberOf<Runtime>) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Nftmart::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn buy_blind_box_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_blind_box(None);
+
+		let _ = Balances::deposit_creating(&CHARLIE, 100);
+		assert_noop!(
+			Nftmart::buy_blind_box(Origin::signed(CHARLIE), CLASS_ID, 1),
+			Error::<Runtime>::AccountNotInWhitelist,
+		);
+		assert_ok!(Nftmart::buy_blind_box(Origin::signed(BOB), CLASS_ID, 2));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::BoughtBlindBox(BOB, CLASS_ID, 2)));
+		assert_eq!(free_balance(&BOB), 80);
+		assert_eq!(token_metadata(TOKEN_ID), Vec::<u8>::new());
+		assert_eq!(unrevealed_tokens(), vec![(TOKEN_ID, 0), (TOKEN_ID + 1, 0)]);
+
+		// the items of the pool are kept for the unrevealed tokens.
+		assert_noop!(
+			Nftmart::buy_blind_box(Origin::signed(BOB), CLASS_ID, 2),
+			Error::<Runtime>::BlindBoxSoldOut,
+		);
+		// the seed of the round is recorded after the purchases of the round.
+		assert_noop!(
+			Nftmart::reveal(Origin::signed(CHARLIE), CLASS_ID),
+			Error::<Runtime>::RevealNotReady,
+		);
+
+		run_to_block(10);
+		assert_eq!(Nftmart::randomness_round(), (1, 10));
+		assert_ok!(Nftmart::reveal(Origin::signed(CHARLIE), CLASS_ID));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RevealedToken(CLASS_ID, TOKEN_ID)));
+		assert_ok!(Nftmart::reveal(Origin::signed(CHARLIE), CLASS_ID));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RevealedToken(CLASS_ID, TOKEN_ID + 1)));
+		assert_noop!(
+			Nftmart::reveal(Origin::signed(CHARLIE), CLASS_ID),
+			Error::<Runtime>::NothingToReveal,
+		);
+
+		let mut drawn = vec![token_metadata(TOKEN_ID), token_metadata(TOKEN_ID + 1)];
+		drawn.extend(Nftmart::blind_boxes(CLASS_ID).unwrap().items);
+		drawn.sort();
+		assert_eq!(drawn, vec![vec![1], vec![2], vec![3]]);
+
+		assert_ok!(Nftmart::remove_blind_box(Origin::signed(class_id_account()), CLASS_ID));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RemovedBlindBox(CLASS_ID)));
+		assert_noop!(
+			Nftmart::buy_blind_box(Origin::signed(BOB), CLASS_ID, 1),
+			Error::<Runtime>::BlindBoxNotFound,
+		);
+	});
+}
+
+#[test]
+fn blind_box_reveal_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		create_blind_box(Some(15));
+
+		assert_ok!(Nftmart::buy_blind_box(Origin::signed(BOB), CLASS_ID, 2));
+		assert_noop!(
+			Nftmart::remove_blind_box(Origin::signed(class_id_account()), CLASS_ID),
+			Error::<Runtime>::UnrevealedTokensExist,
+		);
+		run_to_block(10);
+		assert_noop!(
+			Nftmart::reveal(Origin::signed(CHARLIE), CLASS_ID),
+			Error::<Runtime>::RevealNotReady,
+		);
+
+		run_to_block(15);
+		let reserved = reserved_balance(&class_id_account());
+		assert_ok!(Nftmart::reveal(Origin::signed(CHARLIE), CLASS_ID));
+		assert_eq!(last_event(), Event::nftmart_nft(crate::Event::RevealedToken(CLASS_ID, TOKEN_ID)));
+		assert!(vec![vec![1], vec![2], vec![3]].contains(&token_metadata(TOKEN_ID)));
+		assert_eq!(reserved_balance(&class_id_account()), reserved + 1);
+		assert_eq!(Nftmart::blind_boxes(CLASS_ID).unwrap().items.len(), 2);
+
+		// burning an unrevealed token releases its item.
+		assert_ok!(Nftmart::burn(Origin::signed(BOB), CLASS_ID, TOKEN_ID + 1));
+		assert_eq!(unrevealed_tokens(), vec![]);
+		assert_ok!(Nftmart::buy_blind_box(Origin::signed(BOB), CLASS_ID, 2));
+		assert_eq!(unrevealed_tokens(), vec![(TOKEN_ID + 2, 1), (TOKEN_ID + 3, 1)]);
+		assert_noop!(
+			Nftmart::reveal(Origin::signed(CHARLIE), CLASS_ID),
+			Error::<Runtime>::RevealNotReady,
+		);
+		run_to_block(20);
+		assert_ok!(Nftmart::reveal(Origin::signed(CHARLIE), CLASS_ID));
+		assert_ok!(Nftmart::reveal(Origin::signed(CHARLIE), CLASS_ID));
+		assert_eq!(Nftmart::blind_boxes(CLASS_ID).unwrap().items, Vec::<NFTMetadata>::new());
+	});
+}
+
+#[test]
+fn nft_trait_should_work() {
+	let deposit_token = Nftmart::mint_token_deposit(1, 2).1;
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_category(Origin::root(), vec![1]));
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			vec![1], vec![1], vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable), Some(2)
+		));
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_eq!(<Nftmart as NFT<AccountId>>::class_owner(CLASS_ID), Some(class_id_account()));
+
+		assert_noop!(
+			<Nftmart as NFT<AccountId>>::mint(&ALICE, &BOB, CLASS_ID, vec![1]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			<Nftmart as NFT<AccountId>>::mint(&class_id_account(), &CHARLIE, CLASS_ID, vec![1]),
+			Error::<Runtime>::AccountNotInWhitelist
+		);
+		assert_ok!(NftmartConfig::pause(Origin::root(), CallGroup::Minting));
+		assert_noop!(
+			<Nftmart as NFT<AccountId>>::mint(&class_id_account(), &BOB, CLASS_ID, vec![1]),
+			Error::<Runtime>::CallsPaused
+		);
+		assert_ok!(NftmartConfig::unpause(Origin::root(), CallGroup::Minting));
+		assert_eq!(<Nftmart as NFT<AccountId>>::mint(&class_id_account(), &BOB, CLASS_ID, vec![1]), Ok(0));
+		assert_eq!(<Nftmart as NFT<AccountId>>::mint(&class_id_account(), &BOB, CLASS_ID, vec![1]), Ok(1));
+		assert_noop!(
+			<Nftmart as NFT<AccountId>>::mint(&class_id_account(), &BOB, CLASS_ID, vec![1]),
+			Error::<Runtime>::MaxSupplyExceeded
+		);
+		let event = Event::nftmart_nft(crate::Event::MintedToken(class_id_account(), BOB, CLASS_ID, 1));
+		assert_eq!(last_event(), event);
+		assert_eq!(<Nftmart as NFT<AccountId>>::owner((CLASS_ID, TOKEN_ID)), Some(BOB));
+		assert_eq!(<Nftmart as NFT<AccountId>>::balance(&BOB), 2);
+
+		assert_ok!(NftmartConfig::pause(Origin::root(), CallGroup::Transfers));
+		assert_noop!(
+			<Nftmart as NFT<AccountId>>::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::CallsPaused
+		);
+		assert_ok!(NftmartConfig::unpause(Origin::root(), CallGroup::Transfers));
+		assert_ok!(Nftmart::submit_order(Origin::signed(BOB), NATIVE_CURRENCY_ID, 10, CATEGORY_ID, CLASS_ID, TOKEN_ID, 0, 10, vec![]));
+		assert_noop!(
+			<Nftmart as NFT<AccountId>>::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::OrderExists
+		);
+		assert_ok!(Nftmart::remove_order(Origin::signed(BOB), CLASS_ID, TOKEN_ID));
+		assert_noop!(
+			<Nftmart as NFT<AccountId>>::transfer(&ALICE, &BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(<Nftmart as NFT<AccountId>>::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(<Nftmart as NFT<AccountId>>::owner((CLASS_ID, TOKEN_ID)), Some(ALICE));
+		assert_eq!(<Nftmart as NFT<AccountId>>::balance(&ALICE), 1);
+		assert_eq!(<Nftmart as NFT<AccountId>>::balance(&BOB), 1);
+	});
+}
+
+#[test]
+fn nft_trait_should_respect_transferable() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Nftmart::create_class(
+			Origin::signed(ALICE),
+			vec![1], vec![1], vec![1],
+			Properties(ClassProperty::Burnable.into()), None
+		));
+		let deposit_token = Nftmart::mint_token_deposit(1, 1).1;
+		assert_eq!(Balances::deposit_into_existing(&class_id_account(), deposit_token).is_ok(), true);
+		assert_ok!(<Nftmart as NFT<AccountId>>::mint(&class_id_account(), &BOB, CLASS_ID, vec![1]));
+		assert_noop!(
+			<Nftmart as NFT<AccountId>>::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NonTransferable
+		);
+	});
+}
@@ -0,0 +1,203 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::{ChangeMembers, Contains, InitializeMembers, Time},
+	transactional
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::{data_provider::median, DataFeeder, DataProvider, DataProviderExtended, OnNewData};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+	traits::Saturating,
+	DispatchResult, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+
+pub use module::*;
+
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TimestampedValue<Value, Moment> {
+	/// The value fed or combined.
+	pub value: Value,
+	/// When the value was fed, for a combined value when its median was fed.
+	pub timestamp: Moment,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+	pub type TimestampedValueOf<T> = TimestampedValue<<T as Config>::OracleValue, MomentOf<T>>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The key of fed data, e.g. a currency id.
+		type OracleKey: Parameter + Member;
+
+		/// The value of fed data, e.g. a price.
+		type OracleValue: Parameter + Member + Ord;
+
+		/// Hook called after a value is fed.
+		type OnNewData: OnNewData<Self::AccountId, Self::OracleKey, Self::OracleValue>;
+
+		/// Source of the timestamps.
+		type Time: Time;
+
+		/// The operators allowed to feed values.
+		type Members: Contains<Self::AccountId>;
+
+		/// The minimum number of unexpired operator values needed to combine them.
+		#[pallet::constant]
+		type MinimumCount: Get<u32>;
+
+		/// How long a fed value stays valid.
+		#[pallet::constant]
+		type ExpiresIn: Get<MomentOf<Self>>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The sender is not an operator.
+		NoPermission,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// New values fed by an operator. \[who, values\]
+		NewFeedData(T::AccountId, Vec<(T::OracleKey, T::OracleValue)>),
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	/// The values fed by each operator.
+	#[pallet::storage]
+	#[pallet::getter(fn raw_values)]
+	pub type RawValues<T: Config> = StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::OracleKey, TimestampedValueOf<T>>;
+
+	/// The median of the unexpired operator values.
+	#[pallet::storage]
+	#[pallet::getter(fn values)]
+	pub type Values<T: Config> = StorageMap<_, Twox64Concat, T::OracleKey, TimestampedValueOf<T>>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Feed some values as an operator.
+		///
+		/// - `values`: keys and their new values
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn feed_values(
+			origin: OriginFor<T>,
+			values: Vec<(T::OracleKey, T::OracleValue)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_feed_values(who, values)?;
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn is_valid(value: &TimestampedValueOf<T>, now: MomentOf<T>) -> bool {
+		now.saturating_sub(value.timestamp) < T::ExpiresIn::get()
+	}
+
+	/// Combine the unexpired values of the operators for `key` into their median, which keeps
+	/// its own timestamp so that it expires `ExpiresIn` after the value was actually fed.
+	fn combined(key: &T::OracleKey) -> Option<TimestampedValueOf<T>> {
+		let now = T::Time::now();
+		let values: Vec<TimestampedValueOf<T>> = T::Members::sorted_members()
+			.iter()
+			.filter_map(|who| Self::raw_values(who, key))
+			.filter(|value| Self::is_valid(value, now))
+			.collect();
+
+		if values.len() < T::MinimumCount::get().max(1) as usize {
+			return None;
+		}
+		median(values)
+	}
+
+	/// Recompute the combined value of `key` from the current operator values.
+	fn update_combined(key: &T::OracleKey) {
+		match Self::combined(key) {
+			Some(combined) => Values::<T>::insert(key, combined),
+			None => Values::<T>::remove(key),
+		}
+	}
+
+	fn do_feed_values(who: T::AccountId, values: Vec<(T::OracleKey, T::OracleValue)>) -> DispatchResult {
+		ensure!(T::Members::contains(&who), Error::<T>::NoPermission);
+
+		let now = T::Time::now();
+		for (key, value) in &values {
+			RawValues::<T>::insert(&who, key, TimestampedValue { value: value.clone(), timestamp: now });
+			Self::update_combined(key);
+			T::OnNewData::on_new_data(&who, key, value);
+		}
+		Self::deposit_event(Event::NewFeedData(who, values));
+		Ok(())
+	}
+}
+
+impl<T: Config> DataProvider<T::OracleKey, T::OracleValue> for Pallet<T> {
+	fn get(key: &T::OracleKey) -> Option<T::OracleValue> {
+		Self::get_no_op(key).map(|value| value.value)
+	}
+}
+
+impl<T: Config> DataProviderExtended<T::OracleKey, TimestampedValueOf<T>> for Pallet<T> {
+	fn get_no_op(key: &T::OracleKey) -> Option<TimestampedValueOf<T>> {
+		let now = T::Time::now();
+		Self::values(key).filter(|value| Self::is_valid(value, now))
+	}
+
+	fn get_all_values() -> Vec<(T::OracleKey, Option<TimestampedValueOf<T>>)> {
+		let now = T::Time::now();
+		Values::<T>::iter()
+			.map(|(key, value)| (key, Some(value).filter(|value| Self::is_valid(value, now))))
+			.collect()
+	}
+}
+
+impl<T: Config> DataFeeder<T::OracleKey, T::OracleValue, T::AccountId> for Pallet<T> {
+	fn feed_value(who: T::AccountId, key: T::OracleKey, value: T::OracleValue) -> DispatchResult {
+		Self::do_feed_values(who, sp_std::vec![(key, value)])
+	}
+}
+
+/// Values of removed operators no longer count.
+impl<T: Config> ChangeMembers<T::AccountId> for Pallet<T> {
+	fn change_members_sorted(_incoming: &[T::AccountId], outgoing: &[T::AccountId], _new: &[T::AccountId]) {
+		let mut keys: Vec<T::OracleKey> = Vec::new();
+		for who in outgoing {
+			for (key, _) in RawValues::<T>::drain_prefix(who) {
+				if !keys.contains(&key) {
+					keys.push(key);
+				}
+			}
+		}
+		for key in &keys {
+			Self::update_combined(key);
+		}
+	}
+}
+
+impl<T: Config> InitializeMembers<T::AccountId> for Pallet<T> {
+	fn initialize_members(_members: &[T::AccountId]) {}
+}
@@ -0,0 +1,136 @@
+#![cfg(test)]
+
+use crate as nftmart_oracle;
+use frame_support::{construct_runtime, parameter_types, traits::Contains};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use sp_std::cell::RefCell;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+pub type AccountId = u64;
+pub type Key = u32;
+pub type Value = u32;
+
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1;
+}
+
+impl pallet_timestamp::Config for Runtime {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static MEMBERS: RefCell<Vec<AccountId>> = RefCell::new(vec![ALICE, BOB, CHARLIE]);
+}
+
+pub struct Members;
+impl Members {
+	pub fn set(members: Vec<AccountId>) {
+		MEMBERS.with(|v| *v.borrow_mut() = members);
+	}
+}
+impl Contains<AccountId> for Members {
+	fn sorted_members() -> Vec<AccountId> {
+		MEMBERS.with(|v| v.borrow().clone())
+	}
+}
+
+parameter_types! {
+	pub const MinimumCount: u32 = 2;
+	pub const ExpiresIn: u64 = 600;
+}
+
+impl nftmart_oracle::Config for Runtime {
+	type Event = Event;
+	type OracleKey = Key;
+	type OracleValue = Value;
+	type OnNewData = ();
+	type Time = Timestamp;
+	type Members = Members;
+	type MinimumCount = MinimumCount;
+	type ExpiresIn = ExpiresIn;
+}
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, u32, ()>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
+		Oracle: nftmart_oracle::{Module, Call, Storage, Event<T>},
+	}
+);
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const EVE: AccountId = 5;
+pub const KEY: Key = 1;
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			Timestamp::set_timestamp(1000);
+		});
+		ext
+	}
+}
+
+pub fn last_event() -> Event {
+	frame_system::Module::<Runtime>::events()
+		.pop()
+		.expect("Event expected")
+		.event
+}
@@ -0,0 +1,107 @@
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+#[test]
+fn feed_values_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Oracle::feed_values(Origin::signed(ALICE), vec![(KEY, 100)]));
+		assert_eq!(last_event(), Event::nftmart_oracle(crate::Event::NewFeedData(ALICE, vec![(KEY, 100)])));
+		assert_eq!(Oracle::raw_values(ALICE, KEY), Some(TimestampedValue { value: 100, timestamp: 1000 }));
+		// not enough values to combine
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), None);
+
+		Timestamp::set_timestamp(1100);
+		assert_ok!(Oracle::feed_values(Origin::signed(BOB), vec![(KEY, 300)]));
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), Some(300));
+
+		Timestamp::set_timestamp(1200);
+		assert_ok!(<Oracle as DataFeeder<_, _, _>>::feed_value(CHARLIE, KEY, 200));
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), Some(200));
+		assert_eq!(
+			<Oracle as DataProviderExtended<_, _>>::get_no_op(&KEY),
+			Some(TimestampedValue { value: 200, timestamp: 1200 })
+		);
+		assert_eq!(
+			<Oracle as DataProviderExtended<_, _>>::get_all_values(),
+			vec![(KEY, Some(TimestampedValue { value: 200, timestamp: 1200 }))]
+		);
+	});
+}
+
+#[test]
+fn feed_values_should_expire() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Oracle::feed_values(Origin::signed(ALICE), vec![(KEY, 100)]));
+		Timestamp::set_timestamp(1500);
+		assert_ok!(Oracle::feed_values(Origin::signed(BOB), vec![(KEY, 300)]));
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), Some(300));
+
+		// the value of ALICE expired, only BOB and CHARLIE are combined
+		Timestamp::set_timestamp(2050);
+		assert_ok!(Oracle::feed_values(Origin::signed(CHARLIE), vec![(KEY, 200)]));
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), Some(300));
+
+		// the combined value expires with the feed of its median, from BOB
+		assert_eq!(
+			<Oracle as DataProviderExtended<_, _>>::get_no_op(&KEY),
+			Some(TimestampedValue { value: 300, timestamp: 1500 })
+		);
+		Timestamp::set_timestamp(2099);
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), Some(300));
+		Timestamp::set_timestamp(2100);
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), None);
+		assert_eq!(<Oracle as DataProviderExtended<_, _>>::get_all_values(), vec![(KEY, None)]);
+	});
+}
+
+#[test]
+fn feed_values_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Oracle::feed_values(Origin::signed(EVE), vec![(KEY, 100)]),
+			Error::<Runtime>::NoPermission,
+		);
+	});
+}
+
+#[test]
+fn change_members_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Oracle::feed_values(Origin::signed(ALICE), vec![(KEY, 100)]));
+		assert_ok!(Oracle::feed_values(Origin::signed(BOB), vec![(KEY, 300)]));
+
+		Members::set(vec![BOB, CHARLIE]);
+		<Oracle as ChangeMembers<_>>::change_members_sorted(&[], &[ALICE], &[BOB, CHARLIE]);
+		assert_eq!(Oracle::raw_values(ALICE, KEY), None);
+		assert_noop!(
+			Oracle::feed_values(Origin::signed(ALICE), vec![(KEY, 100)]),
+			Error::<Runtime>::NoPermission,
+		);
+
+		assert_ok!(Oracle::feed_values(Origin::signed(CHARLIE), vec![(KEY, 200)]));
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), Some(300));
+	});
+}
+
+#[test]
+fn change_members_should_update_values() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Oracle::feed_values(Origin::signed(ALICE), vec![(KEY, 100)]));
+		assert_ok!(Oracle::feed_values(Origin::signed(BOB), vec![(KEY, 300)]));
+		assert_ok!(Oracle::feed_values(Origin::signed(CHARLIE), vec![(KEY, 200)]));
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), Some(200));
+
+		Members::set(vec![ALICE, BOB]);
+		<Oracle as ChangeMembers<_>>::change_members_sorted(&[], &[CHARLIE], &[ALICE, BOB]);
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), Some(300));
+
+		// not enough values left to combine
+		Members::set(vec![ALICE]);
+		<Oracle as ChangeMembers<_>>::change_members_sorted(&[], &[BOB], &[ALICE]);
+		assert_eq!(<Oracle as DataProvider<_, _>>::get(&KEY), None);
+		assert_eq!(Oracle::values(KEY), None);
+	});
+}
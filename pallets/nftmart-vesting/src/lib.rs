@@ -0,0 +1,211 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+	pallet_prelude::*,
+	transactional
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::{LockIdentifier, MultiCurrency, MultiLockableCurrency};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+	traits::{
+		AtLeast32Bit, AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedMul, Saturating, StaticLookup,
+		UniqueSaturatedInto, Zero,
+	},
+	DispatchError, DispatchResult, RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+mod mock;
+mod tests;
+
+pub use module::*;
+
+pub const VESTING_LOCK_ID: LockIdentifier = *b"nftmvest";
+
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VestingSchedule<BlockNumber, Balance> {
+	/// `per_period` is unlocked at the end of each `period` since `start`, `period_count` times.
+	Linear {
+		start: BlockNumber,
+		period: BlockNumber,
+		period_count: u32,
+		per_period: Balance,
+	},
+	/// `amount` is unlocked at once at block `at`.
+	Cliff {
+		at: BlockNumber,
+		amount: Balance,
+	},
+}
+
+impl<BlockNumber: AtLeast32Bit + Copy, Balance: AtLeast32BitUnsigned + Copy> VestingSchedule<BlockNumber, Balance> {
+	/// The amount vested by the schedule, `None` on overflow.
+	pub fn total_amount(&self) -> Option<Balance> {
+		match self {
+			Self::Linear { period_count, per_period, .. } => per_period.checked_mul(&(*period_count).into()),
+			Self::Cliff { amount, .. } => Some(*amount),
+		}
+	}
+
+	/// The amount still locked at block `now`.
+	pub fn locked_amount(&self, now: BlockNumber) -> Balance {
+		match self {
+			Self::Linear { start, period, period_count, per_period } => {
+				let elapsed: u32 = now.saturating_sub(*start)
+					.checked_div(period)
+					.unwrap_or_else(Zero::zero)
+					.unique_saturated_into();
+				per_period.saturating_mul(period_count.saturating_sub(elapsed).into())
+			},
+			Self::Cliff { at, amount } => {
+				if now < *at { *amount } else { Zero::zero() }
+			},
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type CurrencyIdOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+	pub type VestingScheduleOf<T> = VestingSchedule<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currencies which can be vested.
+		type MultiCurrency: MultiLockableCurrency<Self::AccountId>;
+
+		/// The minimum amount transferred by `vested_transfer`.
+		#[pallet::constant]
+		type MinVestedTransfer: Get<BalanceOf<Self>>;
+
+		/// The maximum number of vesting schedules per account and currency.
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Period or period count of a linear schedule is zero.
+		InvalidSchedule,
+		/// The vested amount is lower than `MinVestedTransfer`.
+		AmountLow,
+		/// The account has too many vesting schedules.
+		TooManyVestingSchedules,
+		/// Number overflow.
+		NumOverflow,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Added a vesting schedule. \[from, to, currency_id, schedule\]
+		VestingScheduleAdded(T::AccountId, T::AccountId, CurrencyIdOf<T>, VestingScheduleOf<T>),
+		/// Claimed vested balance. \[who, currency_id, locked_amount\]
+		Claimed(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>),
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	/// Vesting schedules of an account in a currency.
+	#[pallet::storage]
+	#[pallet::getter(fn vesting_schedules)]
+	pub type VestingSchedules<T: Config> = StorageDoubleMap<
+		_, Blake2_128Concat, T::AccountId, Twox64Concat, CurrencyIdOf<T>, Vec<VestingScheduleOf<T>>, ValueQuery,
+	>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Unlock the vested balance of `currency_id`.
+		///
+		/// - `currency_id`: currency id
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn claim(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let locked = Self::do_claim(&who, currency_id)?;
+			Self::deposit_event(Event::Claimed(who, currency_id, locked));
+			Ok(().into())
+		}
+
+		/// Transfer some balance of `currency_id` to `dest`, locked by `schedule`.
+		///
+		/// - `dest`: the receiver
+		/// - `currency_id`: currency id
+		/// - `schedule`: how the transferred balance is unlocked
+		#[pallet::weight(100_000)]
+		#[transactional]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			schedule: VestingScheduleOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			Self::do_vested_transfer(&from, &to, currency_id, schedule.clone())?;
+			Self::deposit_event(Event::VestingScheduleAdded(from, to, currency_id, schedule));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn locked_balance(schedules: &[VestingScheduleOf<T>], now: T::BlockNumber) -> Result<BalanceOf<T>, DispatchError> {
+		schedules.iter().try_fold(Zero::zero(), |acc: BalanceOf<T>, schedule| {
+			acc.checked_add(&schedule.locked_amount(now))
+		}).ok_or_else(|| Error::<T>::NumOverflow.into())
+	}
+
+	fn do_claim(who: &T::AccountId, currency_id: CurrencyIdOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		VestingSchedules::<T>::try_mutate_exists(who, currency_id, |maybe_schedules| -> Result<BalanceOf<T>, DispatchError> {
+			let mut schedules = maybe_schedules.take().unwrap_or_default();
+			schedules.retain(|schedule| !schedule.locked_amount(now).is_zero());
+			let locked = Self::locked_balance(&schedules, now)?;
+			if locked.is_zero() {
+				T::MultiCurrency::remove_lock(VESTING_LOCK_ID, currency_id, who)?;
+			} else {
+				T::MultiCurrency::set_lock(VESTING_LOCK_ID, currency_id, who, locked)?;
+				*maybe_schedules = Some(schedules);
+			}
+			Ok(locked)
+		})
+	}
+
+	fn do_vested_transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		schedule: VestingScheduleOf<T>,
+	) -> DispatchResult {
+		if let VestingSchedule::Linear { period, period_count, .. } = &schedule {
+			ensure!(!period.is_zero() && *period_count > 0, Error::<T>::InvalidSchedule);
+		}
+		let amount = schedule.total_amount().ok_or(Error::<T>::NumOverflow)?;
+		ensure!(amount >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		VestingSchedules::<T>::try_mutate(to, currency_id, |schedules| -> DispatchResult {
+			ensure!(schedules.len() < T::MaxVestingSchedules::get() as usize, Error::<T>::TooManyVestingSchedules);
+			schedules.push(schedule);
+			let locked = Self::locked_balance(schedules, now)?;
+
+			T::MultiCurrency::transfer(currency_id, from, to, amount)?;
+			T::MultiCurrency::set_lock(VESTING_LOCK_ID, currency_id, to, locked)?;
+			Ok(())
+		})
+	}
+}
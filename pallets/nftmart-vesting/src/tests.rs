@@ -0,0 +1,101 @@
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+fn free_balance(who: &AccountId) -> Balance {
+	<Tokens as MultiCurrency<AccountId>>::free_balance(CURRENCY_ID, who)
+}
+
+#[test]
+fn vesting_schedule_should_work() {
+	let linear: VestingSchedule<u64, Balance> = VestingSchedule::Linear { start: 10, period: 5, period_count: 3, per_period: 10 };
+	assert_eq!(linear.total_amount(), Some(30));
+	assert_eq!(linear.locked_amount(1), 30);
+	assert_eq!(linear.locked_amount(14), 30);
+	assert_eq!(linear.locked_amount(15), 20);
+	assert_eq!(linear.locked_amount(24), 10);
+	assert_eq!(linear.locked_amount(25), 0);
+	assert_eq!(linear.locked_amount(100), 0);
+
+	let cliff: VestingSchedule<u64, Balance> = VestingSchedule::Cliff { at: 10, amount: 30 };
+	assert_eq!(cliff.total_amount(), Some(30));
+	assert_eq!(cliff.locked_amount(9), 30);
+	assert_eq!(cliff.locked_amount(10), 0);
+
+	let overflow: VestingSchedule<u64, Balance> = VestingSchedule::Linear { start: 0, period: 1, period_count: 2, per_period: Balance::max_value() };
+	assert_eq!(overflow.total_amount(), None);
+}
+
+#[test]
+fn vested_transfer_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let linear = VestingSchedule::Linear { start: 10, period: 5, period_count: 3, per_period: 10 };
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, CURRENCY_ID, linear.clone()));
+		let event = Event::nftmart_vesting(crate::Event::VestingScheduleAdded(ALICE, BOB, CURRENCY_ID, linear.clone()));
+		assert_eq!(last_event(), event);
+		assert_eq!(free_balance(&ALICE), 70);
+		assert_eq!(free_balance(&BOB), 30);
+		assert_eq!(Vesting::vesting_schedules(BOB, CURRENCY_ID), vec![linear.clone()]);
+		assert_noop!(
+			<Tokens as MultiCurrency<AccountId>>::transfer(CURRENCY_ID, &BOB, &ALICE, 1),
+			orml_tokens::Error::<Runtime>::LiquidityRestrictions,
+		);
+
+		let cliff = VestingSchedule::Cliff { at: 20, amount: 20 };
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, CURRENCY_ID, cliff.clone()));
+		assert_eq!(Vesting::vesting_schedules(BOB, CURRENCY_ID), vec![linear.clone(), cliff.clone()]);
+		assert_eq!(free_balance(&BOB), 50);
+
+		System::set_block_number(15);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), CURRENCY_ID));
+		assert_eq!(last_event(), Event::nftmart_vesting(crate::Event::Claimed(BOB, CURRENCY_ID, 40)));
+		assert_ok!(<Tokens as MultiCurrency<AccountId>>::transfer(CURRENCY_ID, &BOB, &ALICE, 10));
+		assert_noop!(
+			<Tokens as MultiCurrency<AccountId>>::transfer(CURRENCY_ID, &BOB, &ALICE, 1),
+			orml_tokens::Error::<Runtime>::LiquidityRestrictions,
+		);
+
+		System::set_block_number(20);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), CURRENCY_ID));
+		assert_eq!(last_event(), Event::nftmart_vesting(crate::Event::Claimed(BOB, CURRENCY_ID, 10)));
+		assert_eq!(Vesting::vesting_schedules(BOB, CURRENCY_ID), vec![linear]);
+
+		System::set_block_number(25);
+		assert_ok!(Vesting::claim(Origin::signed(BOB), CURRENCY_ID));
+		assert_eq!(last_event(), Event::nftmart_vesting(crate::Event::Claimed(BOB, CURRENCY_ID, 0)));
+		assert_eq!(Vesting::vesting_schedules(BOB, CURRENCY_ID), vec![]);
+		assert_eq!(Tokens::locks(BOB, CURRENCY_ID), vec![]);
+		assert_ok!(<Tokens as MultiCurrency<AccountId>>::transfer(CURRENCY_ID, &BOB, &ALICE, 40));
+	});
+}
+
+#[test]
+fn vested_transfer_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Vesting::vested_transfer(Origin::signed(ALICE), BOB, CURRENCY_ID, VestingSchedule::Linear { start: 10, period: 0, period_count: 3, per_period: 10 }),
+			Error::<Runtime>::InvalidSchedule,
+		);
+		assert_noop!(
+			Vesting::vested_transfer(Origin::signed(ALICE), BOB, CURRENCY_ID, VestingSchedule::Linear { start: 10, period: 5, period_count: 0, per_period: 10 }),
+			Error::<Runtime>::InvalidSchedule,
+		);
+		assert_noop!(
+			Vesting::vested_transfer(Origin::signed(ALICE), BOB, CURRENCY_ID, VestingSchedule::Cliff { at: 10, amount: 4 }),
+			Error::<Runtime>::AmountLow,
+		);
+		assert_noop!(
+			Vesting::vested_transfer(Origin::signed(ALICE), BOB, CURRENCY_ID, VestingSchedule::Cliff { at: 10, amount: 101 }),
+			orml_tokens::Error::<Runtime>::BalanceTooLow,
+		);
+
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, CURRENCY_ID, VestingSchedule::Cliff { at: 10, amount: 10 }));
+		assert_ok!(Vesting::vested_transfer(Origin::signed(ALICE), BOB, CURRENCY_ID, VestingSchedule::Cliff { at: 10, amount: 10 }));
+		assert_noop!(
+			Vesting::vested_transfer(Origin::signed(ALICE), BOB, CURRENCY_ID, VestingSchedule::Cliff { at: 10, amount: 10 }),
+			Error::<Runtime>::TooManyVestingSchedules,
+		);
+	});
+}
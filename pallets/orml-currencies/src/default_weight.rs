@@ -26,4 +26,18 @@ impl crate::WeightInfo for () {
 	fn update_balance_native_currency_killing() -> Weight {
 		(62_595_000 as Weight)
 	}
+	fn set_issuer() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	// not benchmarked, `mint` and `burn` update the balance like `update_balance_non_native_currency`
+	// after reading the issuer, and `mint` the registry and the total issuance.
+	fn mint() -> Weight {
+		<() as crate::WeightInfo>::update_balance_non_native_currency()
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+	}
+	fn burn() -> Weight {
+		<() as crate::WeightInfo>::update_balance_non_native_currency()
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+	}
 }
@@ -23,6 +23,8 @@
 //! - `MultiCurrencyExtended` - Extended `MultiCurrency` with additional helper
 //!   types and methods, like updating balance
 //! by a given signed integer amount.
+//! - `NamedMultiReservableCurrency` - Reserves kept apart by an identifier, the
+//!   named reserves of the native currency are recorded by this module.
 //!
 //! ## Interface
 //!
@@ -30,11 +32,18 @@
 //!
 //! - `transfer` - Transfer some balance to another account, in a given
 //!   currency.
+//! - `transfer_keep_alive` - Same as `transfer`, but fails if the sender
+//!   account would be reaped.
 //! - `transfer_native_currency` - Transfer some balance to another account, in
 //!   native currency set in
 //! `Config::NativeCurrency`.
 //! - `update_balance` - Update balance by signed integer amount, in a given
 //!   currency, root origin required.
+//! - `set_issuer` - Assign the issuer and the max supply of a non-native
+//!   currency, `IssuerOrigin` required.
+//! - `mint` - Issue some balance of a currency to an account, only callable by
+//!   the issuer of the currency.
+//! - `burn` - Destroy some balance of a currency held by the issuer.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -43,7 +52,7 @@ use codec::Codec;
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
-		Currency as PalletCurrency, ExistenceRequirement, Get, LockableCurrency as PalletLockableCurrency,
+		Currency as PalletCurrency, ExistenceRequirement, Filter, Get, LockableCurrency as PalletLockableCurrency,
 		ReservableCurrency as PalletReservableCurrency, WithdrawReasons,
 	},
 };
@@ -53,11 +62,12 @@ use orml_traits::{
 	arithmetic::{Signed, SimpleArithmetic},
 	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
 	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
+	NamedMultiReservableCurrency,
 };
 use orml_utilities::with_transaction_result;
 use sp_runtime::{
-	traits::{CheckedSub, MaybeSerializeDeserialize, StaticLookup, Zero},
-	DispatchError, DispatchResult,
+	traits::{CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero},
+	DispatchError, DispatchResult, RuntimeDebug,
 };
 use sp_std::{
 	convert::{TryFrom, TryInto},
@@ -71,6 +81,15 @@ mod tests;
 
 pub use module::*;
 
+/// The issuer of a currency and the cap on its total issuance.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct IssuerInfo<AccountId, Balance> {
+	/// The account allowed to mint and burn the currency.
+	pub issuer: AccountId,
+	/// Total issuance can not exceed `max_supply` by minting.
+	pub max_supply: Balance,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -81,6 +100,9 @@ pub mod module {
 		fn update_balance_non_native_currency() -> Weight;
 		fn update_balance_native_currency_creating() -> Weight;
 		fn update_balance_native_currency_killing() -> Weight;
+		fn set_issuer() -> Weight;
+		fn mint() -> Weight;
+		fn burn() -> Weight;
 	}
 
 	pub(crate) type BalanceOf<T> =
@@ -89,6 +111,9 @@ pub mod module {
 		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
 	pub(crate) type AmountOf<T> =
 		<<T as Config>::MultiCurrency as MultiCurrencyExtended<<T as frame_system::Config>::AccountId>>::Amount;
+	pub(crate) type ReserveIdentifierOf<T> = <<T as Config>::MultiCurrency as NamedMultiReservableCurrency<
+		<T as frame_system::Config>::AccountId,
+	>>::ReserveIdentifier;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -97,7 +122,8 @@ pub mod module {
 		type MultiCurrency: MergeAccount<Self::AccountId>
 			+ MultiCurrencyExtended<Self::AccountId>
 			+ MultiLockableCurrency<Self::AccountId>
-			+ MultiReservableCurrency<Self::AccountId>;
+			+ MultiReservableCurrency<Self::AccountId>
+			+ NamedMultiReservableCurrency<Self::AccountId>;
 
 		type NativeCurrency: BasicCurrencyExtended<Self::AccountId, Balance = BalanceOf<Self>, Amount = AmountOf<Self>>
 			+ BasicLockableCurrency<Self::AccountId, Balance = BalanceOf<Self>>
@@ -106,6 +132,12 @@ pub mod module {
 		#[pallet::constant]
 		type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
 
+		/// The origin which may assign the issuer of a currency.
+		type IssuerOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Currencies which may be issued by an issuer.
+		type IssuableCurrencies: Filter<CurrencyIdOf<Self>>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -116,6 +148,14 @@ pub mod module {
 		AmountIntoBalanceFailed,
 		/// Balance is too low.
 		BalanceTooLow,
+		/// The currency can not be issued.
+		CurrencyNotIssuable,
+		/// The currency has no issuer.
+		IssuerNotFound,
+		/// The sender is not the issuer of the currency.
+		NotIssuer,
+		/// Minting would exceed the max supply of the currency.
+		MaxSupplyExceeded,
 	}
 
 	#[pallet::event]
@@ -129,7 +169,30 @@ pub mod module {
 		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
 		/// Withdraw success. [currency_id, who, amount]
 		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
-	}
+		/// Issuer set. [currency_id, issuer, max_supply]
+		IssuerSet(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Mint success. [currency_id, who, amount]
+		Minted(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Burn success. [currency_id, who, amount]
+		Burned(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+	}
+
+	/// The issuer of a currency.
+	#[pallet::storage]
+	#[pallet::getter(fn issuers)]
+	pub type Issuers<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, IssuerInfo<T::AccountId, BalanceOf<T>>>;
+
+	/// The native currency reserved by an account under an identifier.
+	///
+	/// The plain reserve calls of this pallet leave it untouched, but it is
+	/// only bookkeeping over the reserved balance of `NativeCurrency`: other
+	/// pallets unreserving or slashing the native currency directly may still
+	/// consume funds reserved under an identifier.
+	#[pallet::storage]
+	#[pallet::getter(fn native_reserves)]
+	pub type NativeReserves<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, ReserveIdentifierOf<T>, BalanceOf<T>, ValueQuery>;
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
@@ -156,6 +219,24 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Transfer some balance to another account under `currency_id`,
+		/// failing if the transactor account would be reaped.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_non_native_currency())]
+		pub fn transfer_keep_alive(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			<Self as MultiCurrency<T::AccountId>>::transfer_keep_alive(currency_id, &from, &to, amount)?;
+			Ok(().into())
+		}
+
 		/// Transfer some native currency to another account.
 		///
 		/// The dispatch origin for this call must be `Signed` by the
@@ -189,6 +270,109 @@ pub mod module {
 			<Self as MultiCurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
 			Ok(().into())
 		}
+
+		/// Set the issuer and the max supply of `currency_id`.
+		///
+		/// The dispatch origin of this call must be `IssuerOrigin`.
+		#[pallet::weight(T::WeightInfo::set_issuer())]
+		pub fn set_issuer(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			issuer: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] max_supply: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::IssuerOrigin::ensure_origin(origin)?;
+			Self::ensure_issuable(currency_id)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			Issuers::<T>::insert(
+				currency_id,
+				IssuerInfo {
+					issuer: issuer.clone(),
+					max_supply,
+				},
+			);
+			Self::deposit_event(Event::IssuerSet(currency_id, issuer, max_supply));
+			Ok(().into())
+		}
+
+		/// Mint some balance of `currency_id` to `dest`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the issuer of
+		/// `currency_id`.
+		#[pallet::weight(T::WeightInfo::mint())]
+		pub fn mint(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_issuable(currency_id)?;
+			let info = Self::issuers(currency_id).ok_or(Error::<T>::IssuerNotFound)?;
+			ensure!(info.issuer == who, Error::<T>::NotIssuer);
+			let total_issuance = T::MultiCurrency::total_issuance(currency_id)
+				.checked_add(&amount)
+				.ok_or(Error::<T>::MaxSupplyExceeded)?;
+			ensure!(total_issuance <= info.max_supply, Error::<T>::MaxSupplyExceeded);
+
+			T::MultiCurrency::deposit(currency_id, &dest, amount)?;
+			Self::deposit_event(Event::Minted(currency_id, dest, amount));
+			Ok(().into())
+		}
+
+		/// Burn some balance of `currency_id` held by the issuer.
+		///
+		/// The dispatch origin for this call must be `Signed` by the issuer of
+		/// `currency_id`.
+		#[pallet::weight(T::WeightInfo::burn())]
+		pub fn burn(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let info = Self::issuers(currency_id).ok_or(Error::<T>::IssuerNotFound)?;
+			ensure!(info.issuer == who, Error::<T>::NotIssuer);
+
+			T::MultiCurrency::withdraw(currency_id, &who, amount)?;
+			Self::deposit_event(Event::Burned(currency_id, who, amount));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The reserved native balance of `who` not reserved under any identifier.
+	fn unnamed_native_reserved(who: &T::AccountId) -> BalanceOf<T> {
+		let named = NativeReserves::<T>::iter_prefix_values(who)
+			.fold(Zero::zero(), |total: BalanceOf<T>, amount| total.saturating_add(amount));
+		T::NativeCurrency::reserved_balance(who).saturating_sub(named)
+	}
+
+	/// Take up to `value` from the native reserve `id` of `who`, `apply`
+	/// returning the amount actually moved out of it. Returns the amount of
+	/// `value` that was not taken.
+	fn take_native_reserve(
+		id: &ReserveIdentifierOf<T>,
+		who: &T::AccountId,
+		value: BalanceOf<T>,
+		apply: impl FnOnce(BalanceOf<T>) -> BalanceOf<T>,
+	) -> BalanceOf<T> {
+		NativeReserves::<T>::mutate_exists(who, id, |maybe_reserved| {
+			let reserved = maybe_reserved.unwrap_or_else(Zero::zero);
+			let actual = apply(reserved.min(value));
+			*maybe_reserved = Some(reserved - actual).filter(|left| !left.is_zero());
+			value - actual
+		})
+	}
+
+	fn ensure_issuable(currency_id: CurrencyIdOf<T>) -> DispatchResult {
+		ensure!(
+			currency_id != T::GetNativeCurrencyId::get() && T::IssuableCurrencies::filter(&currency_id),
+			Error::<T>::CurrencyNotIssuable
+		);
+		Ok(())
 	}
 }
 
@@ -254,6 +438,24 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
+	fn transfer_keep_alive(
+		currency_id: Self::CurrencyId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() || from == to {
+			return Ok(());
+		}
+		if currency_id == T::GetNativeCurrencyId::get() {
+			T::NativeCurrency::transfer_keep_alive(from, to, amount)?;
+		} else {
+			T::MultiCurrency::transfer_keep_alive(currency_id, from, to, amount)?;
+		}
+		Self::deposit_event(Event::Transferred(currency_id, from.clone(), to.clone(), amount));
+		Ok(())
+	}
+
 	fn deposit(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
@@ -360,7 +562,8 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
 		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::slash_reserved(who, value)
+			let actual = Self::unnamed_native_reserved(who).min(value);
+			value - (actual - T::NativeCurrency::slash_reserved(who, actual))
 		} else {
 			T::MultiCurrency::slash_reserved(currency_id, who, value)
 		}
@@ -384,7 +587,8 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 
 	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
 		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::unreserve(who, value)
+			let actual = Self::unnamed_native_reserved(who).min(value);
+			value - (actual - T::NativeCurrency::unreserve(who, actual))
 		} else {
 			T::MultiCurrency::unreserve(currency_id, who, value)
 		}
@@ -398,13 +602,116 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		status: BalanceStatus,
 	) -> result::Result<Self::Balance, DispatchError> {
 		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::repatriate_reserved(slashed, beneficiary, value, status)
+			let actual = Self::unnamed_native_reserved(slashed).min(value);
+			let remaining = T::NativeCurrency::repatriate_reserved(slashed, beneficiary, actual, status)?;
+			Ok(value - (actual - remaining))
 		} else {
 			T::MultiCurrency::repatriate_reserved(currency_id, slashed, beneficiary, value, status)
 		}
 	}
 }
 
+impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
+	type ReserveIdentifier = ReserveIdentifierOf<T>;
+
+	fn reserved_balance_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+	) -> Self::Balance {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			Self::native_reserves(who, id)
+		} else {
+			T::MultiCurrency::reserved_balance_named(id, currency_id, who)
+		}
+	}
+
+	fn reserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> DispatchResult {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			if value.is_zero() {
+				return Ok(());
+			}
+			T::NativeCurrency::reserve(who, value)?;
+			NativeReserves::<T>::mutate(who, id, |reserved| *reserved = reserved.saturating_add(value));
+			Ok(())
+		} else {
+			T::MultiCurrency::reserve_named(id, currency_id, who, value)
+		}
+	}
+
+	fn unreserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::Balance {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			if value.is_zero() {
+				return value;
+			}
+			Self::take_native_reserve(id, who, value, |to_change| {
+				to_change - T::NativeCurrency::unreserve(who, to_change)
+			})
+		} else {
+			T::MultiCurrency::unreserve_named(id, currency_id, who, value)
+		}
+	}
+
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::Balance {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			if value.is_zero() {
+				return value;
+			}
+			Self::take_native_reserve(id, who, value, |to_change| {
+				to_change - T::NativeCurrency::slash_reserved(who, to_change)
+			})
+		} else {
+			T::MultiCurrency::slash_reserved_named(id, currency_id, who, value)
+		}
+	}
+
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError> {
+		if currency_id != T::GetNativeCurrencyId::get() {
+			return T::MultiCurrency::repatriate_reserved_named(id, currency_id, slashed, beneficiary, value, status);
+		}
+		if value.is_zero() {
+			return Ok(value);
+		}
+
+		if slashed == beneficiary {
+			return match status {
+				BalanceStatus::Free => Ok(Self::unreserve_named(id, currency_id, slashed, value)),
+				BalanceStatus::Reserved => Ok(value.saturating_sub(Self::native_reserves(slashed, id))),
+			};
+		}
+
+		let to_change = Self::native_reserves(slashed, id).min(value);
+		let actual = to_change - T::NativeCurrency::repatriate_reserved(slashed, beneficiary, to_change, status)?;
+		if status == BalanceStatus::Reserved {
+			NativeReserves::<T>::mutate(beneficiary, id, |reserved| *reserved = reserved.saturating_add(actual));
+		}
+		Self::take_native_reserve(id, slashed, actual, |to_change| to_change);
+		Ok(value - actual)
+	}
+}
+
 pub struct Currency<T, GetCurrencyId>(marker::PhantomData<T>, marker::PhantomData<GetCurrencyId>);
 
 impl<T, GetCurrencyId> BasicCurrency<T::AccountId> for Currency<T, GetCurrencyId>
@@ -438,6 +745,10 @@ where
 		<Pallet<T> as MultiCurrency<T::AccountId>>::transfer(GetCurrencyId::get(), from, to, amount)
 	}
 
+	fn transfer_keep_alive(from: &T::AccountId, to: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		<Pallet<T> as MultiCurrency<T::AccountId>>::transfer_keep_alive(GetCurrencyId::get(), from, to, amount)
+	}
+
 	fn deposit(who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
 		<Pallet<T>>::deposit(GetCurrencyId::get(), who, amount)
 	}
@@ -572,6 +883,10 @@ where
 		Currency::transfer(from, to, amount, ExistenceRequirement::AllowDeath)
 	}
 
+	fn transfer_keep_alive(from: &AccountId, to: &AccountId, amount: Self::Balance) -> DispatchResult {
+		Currency::transfer(from, to, amount, ExistenceRequirement::KeepAlive)
+	}
+
 	fn deposit(who: &AccountId, amount: Self::Balance) -> DispatchResult {
 		let _ = Currency::deposit_creating(who, amount);
 		Ok(())
@@ -69,6 +69,8 @@ parameter_type_with_key! {
 
 parameter_types! {
 	pub DustAccount: AccountId = ModuleId(*b"orml/dst").into_account();
+	pub const ExemptModuleAccounts: bool = true;
+	pub const MaxReserves: u32 = 50;
 }
 
 impl orml_tokens::Config for Runtime {
@@ -79,6 +81,9 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = orml_tokens::TransferDust<Runtime, DustAccount>;
+	type ExemptModuleAccounts = ExemptModuleAccounts;
+	type ReserveIdentifier = [u8; 8];
+	type MaxReserves = MaxReserves;
 }
 
 pub const NATIVE_CURRENCY_ID: CurrencyId = 1;
@@ -93,6 +98,8 @@ impl Config for Runtime {
 	type MultiCurrency = Tokens;
 	type NativeCurrency = AdaptedBasicCurrency;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type IssuerOrigin = frame_system::EnsureRoot<AccountId>;
+	type IssuableCurrencies = ();
 	type WeightInfo = ();
 }
 pub type NativeCurrency = NativeCurrencyOf<Runtime>;
@@ -108,7 +115,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Storage, Config, Event<T>},
-		Currencies: currencies::{Module, Call, Event<T>},
+		Currencies: currencies::{Module, Call, Storage, Event<T>},
 		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
 		PalletBalances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 	}
@@ -118,6 +125,8 @@ pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
 pub const BOB: AccountId = AccountId32::new([2u8; 32]);
 pub const EVA: AccountId = AccountId32::new([5u8; 32]);
 pub const ID_1: LockIdentifier = *b"1       ";
+pub const RID_1: [u8; 8] = [1u8; 8];
+pub const RID_2: [u8; 8] = [2u8; 8];
 
 pub struct ExtBuilder {
 	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
@@ -38,6 +38,66 @@ fn multi_reservable_currency_should_work() {
 		});
 }
 
+#[test]
+fn named_multi_reservable_currency_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			for currency_id in [NATIVE_CURRENCY_ID, X_TOKEN_ID].iter().copied() {
+				assert_ok!(Currencies::reserve(currency_id, &ALICE, 10));
+				assert_ok!(Currencies::reserve_named(&RID_1, currency_id, &ALICE, 30));
+				assert_ok!(Currencies::reserve_named(&RID_2, currency_id, &ALICE, 20));
+				assert_eq!(Currencies::reserved_balance(currency_id, &ALICE), 60);
+				assert_eq!(Currencies::reserved_balance_named(&RID_1, currency_id, &ALICE), 30);
+
+				assert_eq!(Currencies::unreserve_named(&RID_1, currency_id, &ALICE, 50), 20);
+				assert_eq!(Currencies::reserved_balance_named(&RID_1, currency_id, &ALICE), 0);
+				assert_eq!(Currencies::reserved_balance(currency_id, &ALICE), 30);
+
+				assert_eq!(Currencies::slash_reserved_named(&RID_2, currency_id, &ALICE, 5), 0);
+				assert_eq!(Currencies::reserved_balance_named(&RID_2, currency_id, &ALICE), 15);
+
+				assert_eq!(
+					Currencies::repatriate_reserved_named(&RID_2, currency_id, &ALICE, &BOB, 20, BalanceStatus::Reserved),
+					Ok(5)
+				);
+				assert_eq!(Currencies::reserved_balance_named(&RID_2, currency_id, &ALICE), 0);
+				assert_eq!(Currencies::reserved_balance_named(&RID_2, currency_id, &BOB), 15);
+				assert_eq!(Currencies::reserved_balance(currency_id, &ALICE), 10);
+				assert_eq!(Currencies::free_balance(currency_id, &ALICE), 70);
+			}
+			assert_eq!(Currencies::native_reserves(&ALICE, &RID_1), 0);
+			assert_eq!(NativeReserves::<Runtime>::contains_key(&ALICE, &RID_1), false);
+		});
+}
+
+#[test]
+fn plain_reserve_calls_should_keep_named_reserves() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			for currency_id in [NATIVE_CURRENCY_ID, X_TOKEN_ID].iter().copied() {
+				assert_ok!(Currencies::reserve(currency_id, &ALICE, 10));
+				assert_ok!(Currencies::reserve_named(&RID_1, currency_id, &ALICE, 30));
+
+				assert_eq!(Currencies::unreserve(currency_id, &ALICE, 5), 0);
+				assert_eq!(Currencies::slash_reserved(currency_id, &ALICE, 10), 5);
+				assert_eq!(
+					Currencies::repatriate_reserved(currency_id, &ALICE, &BOB, 10, BalanceStatus::Free),
+					Ok(10)
+				);
+				assert_eq!(Currencies::unreserve(currency_id, &ALICE, 10), 10);
+				assert_eq!(Currencies::reserved_balance(currency_id, &ALICE), 30);
+				assert_eq!(Currencies::reserved_balance_named(&RID_1, currency_id, &ALICE), 30);
+
+				assert_eq!(Currencies::unreserve_named(&RID_1, currency_id, &ALICE, 30), 0);
+				assert_eq!(Currencies::free_balance(currency_id, &ALICE), 95);
+			}
+		});
+}
+
 #[test]
 fn native_currency_lockable_should_work() {
 	ExtBuilder::default()
@@ -98,6 +158,35 @@ fn multi_currency_should_work() {
 		});
 }
 
+#[test]
+fn transfer_keep_alive_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				Currencies::transfer_keep_alive(Some(ALICE).into(), BOB, X_TOKEN_ID, 100),
+				orml_tokens::Error::<Runtime>::KeepAlive,
+			);
+			assert_ok!(Currencies::transfer_keep_alive(Some(ALICE).into(), BOB, X_TOKEN_ID, 99));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 1);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 199);
+
+			let transferred_event = Event::currencies(crate::Event::Transferred(X_TOKEN_ID, ALICE, BOB, 99));
+			assert!(System::events().iter().any(|record| record.event == transferred_event));
+
+			assert_noop!(
+				Currencies::transfer_keep_alive(Some(ALICE).into(), BOB, NATIVE_CURRENCY_ID, 100),
+				pallet_balances::Error::<Runtime, _>::KeepAlive,
+			);
+			assert_ok!(Currencies::transfer_keep_alive(Some(ALICE).into(), BOB, NATIVE_CURRENCY_ID, 99));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 1);
+			assert_eq!(NativeCurrency::free_balance(&BOB), 199);
+		});
+}
+
 #[test]
 fn multi_currency_extended_should_work() {
 	ExtBuilder::default()
@@ -284,3 +373,76 @@ fn call_event_should_work() {
 			assert!(System::events().iter().any(|record| record.event == transferred_event));
 		});
 }
+
+#[test]
+fn issuer_mint_and_burn_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Currencies::set_issuer(Origin::root(), X_TOKEN_ID, EVA, 300));
+			let issuer_event = Event::currencies(crate::Event::IssuerSet(X_TOKEN_ID, EVA, 300));
+			assert!(System::events().iter().any(|record| record.event == issuer_event));
+			assert_eq!(
+				Currencies::issuers(X_TOKEN_ID),
+				Some(IssuerInfo {
+					issuer: EVA,
+					max_supply: 300
+				})
+			);
+
+			assert_ok!(Currencies::mint(Origin::signed(EVA), ALICE, X_TOKEN_ID, 50));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 150);
+			assert_eq!(Currencies::total_issuance(X_TOKEN_ID), 250);
+			let minted_event = Event::currencies(crate::Event::Minted(X_TOKEN_ID, ALICE, 50));
+			assert!(System::events().iter().any(|record| record.event == minted_event));
+
+			assert_ok!(Currencies::mint(Origin::signed(EVA), EVA, X_TOKEN_ID, 50));
+			assert_ok!(Currencies::burn(Origin::signed(EVA), X_TOKEN_ID, 20));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &EVA), 30);
+			assert_eq!(Currencies::total_issuance(X_TOKEN_ID), 280);
+			let burned_event = Event::currencies(crate::Event::Burned(X_TOKEN_ID, EVA, 20));
+			assert!(System::events().iter().any(|record| record.event == burned_event));
+		});
+}
+
+#[test]
+fn issuer_mint_and_burn_should_fail() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Currencies::set_issuer(Origin::signed(ALICE), X_TOKEN_ID, EVA, 300),
+				BadOrigin
+			);
+			assert_noop!(
+				Currencies::set_issuer(Origin::root(), NATIVE_CURRENCY_ID, EVA, 300),
+				Error::<Runtime>::CurrencyNotIssuable
+			);
+			assert_noop!(
+				Currencies::mint(Origin::signed(EVA), ALICE, X_TOKEN_ID, 50),
+				Error::<Runtime>::IssuerNotFound
+			);
+
+			assert_ok!(Currencies::set_issuer(Origin::root(), X_TOKEN_ID, EVA, 300));
+			assert_noop!(
+				Currencies::mint(Origin::signed(ALICE), ALICE, X_TOKEN_ID, 50),
+				Error::<Runtime>::NotIssuer
+			);
+			assert_noop!(
+				Currencies::burn(Origin::signed(ALICE), X_TOKEN_ID, 50),
+				Error::<Runtime>::NotIssuer
+			);
+			assert_noop!(
+				Currencies::mint(Origin::signed(EVA), ALICE, X_TOKEN_ID, 101),
+				Error::<Runtime>::MaxSupplyExceeded
+			);
+			assert_noop!(
+				Currencies::burn(Origin::signed(EVA), X_TOKEN_ID, 1),
+				orml_tokens::Error::<Runtime>::BalanceTooLow
+			);
+		});
+}
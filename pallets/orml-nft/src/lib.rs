@@ -17,14 +17,17 @@
 //! - `mint` - Mint NFT(non fungible token)
 //! - `burn` - Burn NFT(non fungible token)
 //! - `destroy_class` - Destroy NFT(non fungible token) class
+//!
+//! The module also implements the `orml_traits::NFT` trait.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
 use codec::{Decode, Encode};
 use frame_support::{ensure, pallet_prelude::*, Parameter};
+use orml_traits::NFT;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One, Zero},
+	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One, Saturating, Zero},
 	DispatchError, DispatchResult, RuntimeDebug,
 };
 use sp_std::vec::Vec;
@@ -144,6 +147,18 @@ pub mod module {
 	pub type TokensByOwner<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, (T::ClassId, T::TokenId), (), ValueQuery>;
 
+	/// The number of tokens owned by an account, of all classes.
+	#[pallet::storage]
+	#[pallet::getter(fn token_balances)]
+	pub type TokenBalances<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::TokenId, ValueQuery>;
+
+	/// The next token `migrate_token_balances` counts into `TokenBalances`, `None` once all are counted.
+	///
+	/// Until then the balances only include the tokens before the cursor.
+	#[pallet::storage]
+	#[pallet::getter(fn token_balances_cursor)]
+	pub type TokenBalancesCursor<T: Config> = StorageValue<_, (T::ClassId, T::TokenId)>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub tokens: Vec<GenesisTokens<T>>,
@@ -215,6 +230,8 @@ impl<T: Config> Pallet<T> {
 			}
 
 			info.owner = to.clone();
+			Self::mutate_token_balance(from, token, |balance| *balance = balance.saturating_sub(One::one()));
+			Self::mutate_token_balance(to, token, |balance| *balance = balance.saturating_add(One::one()));
 
 			#[cfg(not(feature = "disable-tokens-by-owner"))]
 			{
@@ -252,6 +269,7 @@ impl<T: Config> Pallet<T> {
 				data,
 			};
 			Tokens::<T>::insert(class_id, token_id, token_info);
+			Self::mutate_token_balance(owner, (class_id, token_id), |balance| *balance = balance.saturating_add(One::one()));
 			#[cfg(not(feature = "disable-tokens-by-owner"))]
 			TokensByOwner::<T>::insert(owner, (class_id, token_id), ());
 
@@ -273,6 +291,7 @@ impl<T: Config> Pallet<T> {
 					.ok_or(Error::<T>::NumOverflow)?;
 				Ok(())
 			})?;
+			Self::mutate_token_balance(owner, token, |balance| *balance = balance.saturating_sub(One::one()));
 
 			#[cfg(not(feature = "disable-tokens-by-owner"))]
 			TokensByOwner::<T>::remove(owner, token);
@@ -301,4 +320,91 @@ impl<T: Config> Pallet<T> {
 		#[cfg(not(feature = "disable-tokens-by-owner"))]
 		TokensByOwner::<T>::contains_key(account, token)
 	}
+
+	/// Change the balance of `who` for `token`, unless `migrate_token_balances` has yet to count it.
+	fn mutate_token_balance(who: &T::AccountId, token: (T::ClassId, T::TokenId), f: impl FnOnce(&mut T::TokenId)) {
+		if Self::token_balances_cursor().map_or(true, |cursor| token < cursor) {
+			TokenBalances::<T>::mutate(who, f);
+		}
+	}
+
+	/// Start counting the existing tokens into `TokenBalances`, which must be empty.
+	///
+	/// The tokens are counted over the following blocks by `migrate_token_balances`.
+	pub fn start_token_balances_migration() -> Weight {
+		TokenBalancesCursor::<T>::put((T::ClassId::zero(), T::TokenId::zero()));
+		T::DbWeight::get().writes(1)
+	}
+
+	/// Count at most `limit` token ids from the cursor into `TokenBalances`.
+	///
+	/// The ids of all classes up to `NextTokenId` are visited once, burned ones included, so a chain
+	/// with `n` minted tokens is migrated after about `n / limit` calls.
+	pub fn migrate_token_balances(limit: u32) -> Weight {
+		let (mut class_id, mut token_id) = match Self::token_balances_cursor() {
+			Some(cursor) => cursor,
+			None => return T::DbWeight::get().reads(1),
+		};
+		let next_class_id = Self::next_class_id();
+		let mut next_token_id = Self::next_token_id(class_id);
+		let (mut reads, mut writes): (Weight, Weight) = (3, 1);
+		for _ in 0..limit {
+			if token_id >= next_token_id {
+				class_id = class_id.saturating_add(One::one());
+				if class_id >= next_class_id {
+					TokenBalancesCursor::<T>::kill();
+					return T::DbWeight::get().reads_writes(reads, writes);
+				}
+				token_id = Zero::zero();
+				next_token_id = Self::next_token_id(class_id);
+				reads += 1;
+				continue;
+			}
+			reads += 1;
+			if let Some(token) = Self::tokens(class_id, token_id) {
+				TokenBalances::<T>::mutate(token.owner, |balance| *balance = balance.saturating_add(One::one()));
+				reads += 1;
+				writes += 1;
+			}
+			token_id = token_id.saturating_add(One::one());
+		}
+		TokenBalancesCursor::<T>::put((class_id, token_id));
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
+impl<T: Config> NFT<T::AccountId> for Pallet<T>
+where
+	T::TokenData: Default,
+{
+	type ClassId = T::ClassId;
+	type TokenId = T::TokenId;
+	type Balance = T::TokenId;
+
+	fn balance(who: &T::AccountId) -> Self::Balance {
+		Self::token_balances(who)
+	}
+
+	fn owner(token: (Self::ClassId, Self::TokenId)) -> Option<T::AccountId> {
+		Self::tokens(token.0, token.1).map(|info| info.owner)
+	}
+
+	fn transfer(from: &T::AccountId, to: &T::AccountId, token: (Self::ClassId, Self::TokenId)) -> DispatchResult {
+		Self::transfer(from, to, token)
+	}
+
+	fn class_owner(class_id: Self::ClassId) -> Option<T::AccountId> {
+		Self::classes(class_id).map(|info| info.owner)
+	}
+
+	fn mint(
+		class_owner: &T::AccountId,
+		to: &T::AccountId,
+		class_id: Self::ClassId,
+		metadata: Vec<u8>,
+	) -> Result<Self::TokenId, DispatchError> {
+		let info = Self::classes(class_id).ok_or(Error::<T>::ClassNotFound)?;
+		ensure!(info.owner == *class_owner, Error::<T>::NoPermission);
+		Self::mint(to, class_id, metadata, Default::default())
+	}
 }
@@ -178,3 +178,68 @@ fn destroy_class_should_fail() {
 		assert_eq!(Classes::<Runtime>::contains_key(CLASS_ID), false);
 	});
 }
+
+#[test]
+fn token_balances_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_eq!(NonFungibleTokenModule::token_balances(BOB), 2);
+
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &BOB, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NonFungibleTokenModule::token_balances(BOB), 2);
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NonFungibleTokenModule::token_balances(ALICE), 1);
+		assert_eq!(NonFungibleTokenModule::token_balances(BOB), 1);
+
+		assert_ok!(NonFungibleTokenModule::burn(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NonFungibleTokenModule::token_balances(ALICE), 0);
+
+		TokenBalances::<Runtime>::remove_all();
+		NonFungibleTokenModule::start_token_balances_migration();
+		NonFungibleTokenModule::migrate_token_balances(2);
+		assert_eq!(NonFungibleTokenModule::token_balances_cursor(), Some((CLASS_ID, 2)));
+		assert_eq!(NonFungibleTokenModule::token_balances(BOB), 1);
+
+		// tokens before the cursor are counted, the others are left to the migration.
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, 1)));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_eq!(NonFungibleTokenModule::token_balances(ALICE), 1);
+		assert_eq!(NonFungibleTokenModule::token_balances(BOB), 0);
+
+		NonFungibleTokenModule::migrate_token_balances(10);
+		assert_eq!(NonFungibleTokenModule::token_balances_cursor(), None);
+		assert_eq!(NonFungibleTokenModule::token_balances(ALICE), 1);
+		assert_eq!(NonFungibleTokenModule::token_balances(BOB), 1);
+
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, 2)));
+		assert_eq!(NonFungibleTokenModule::token_balances(ALICE), 2);
+		assert_eq!(NonFungibleTokenModule::token_balances(BOB), 0);
+	});
+}
+
+#[test]
+fn nft_trait_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(<NonFungibleTokenModule as NFT<AccountId>>::class_owner(CLASS_ID), None);
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_eq!(<NonFungibleTokenModule as NFT<AccountId>>::class_owner(CLASS_ID), Some(ALICE));
+
+		assert_noop!(
+			<NonFungibleTokenModule as NFT<AccountId>>::mint(&BOB, &BOB, CLASS_ID, vec![1]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_eq!(
+			<NonFungibleTokenModule as NFT<AccountId>>::mint(&ALICE, &BOB, CLASS_ID, vec![1]),
+			Ok(TOKEN_ID)
+		);
+		assert_eq!(<NonFungibleTokenModule as NFT<AccountId>>::owner((CLASS_ID, TOKEN_ID)), Some(BOB));
+		assert_eq!(<NonFungibleTokenModule as NFT<AccountId>>::balance(&BOB), 1);
+
+		assert_ok!(<NonFungibleTokenModule as NFT<AccountId>>::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(<NonFungibleTokenModule as NFT<AccountId>>::owner((CLASS_ID, TOKEN_ID)), Some(ALICE));
+		assert_eq!(<NonFungibleTokenModule as NFT<AccountId>>::balance(&BOB), 0);
+		assert_eq!(<NonFungibleTokenModule as NFT<AccountId>>::balance(&ALICE), 1);
+	});
+}
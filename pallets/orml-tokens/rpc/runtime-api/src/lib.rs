@@ -0,0 +1,33 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+pub use sp_core::constants_types::{Balance, CurrencyId};
+use sp_runtime::RuntimeDebug;
+pub use sp_std::vec::Vec;
+
+/// The balance of an account in one currency.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BalanceInfo<Balance> {
+	/// The transferable part of the balance, including the frozen part.
+	pub free: Balance,
+	/// The reserved part of the balance.
+	pub reserved: Balance,
+	/// The amount that `free` may not drop below.
+	pub frozen: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to query balances of all currencies, the native one included.
+	pub trait TokensApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The non-empty balances of `who`, by currency.
+		fn account_balances(who: AccountId) -> Vec<(CurrencyId, BalanceInfo<Balance>)>;
+		/// The total issuance of `currency_id`.
+		fn total_issuance(currency_id: CurrencyId) -> Balance;
+	}
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+use codec::Codec;
+use sp_blockchain::HeaderBackend;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT}};
+use sp_api::ProvideRuntimeApi;
+pub use orml_tokens_rpc_runtime_api::TokensApi as TokensRuntimeApi;
+use orml_tokens_rpc_runtime_api::{BalanceInfo, CurrencyId};
+
+#[rpc]
+pub trait TokensApi<AccountId> {
+	#[rpc(name = "tokens_accountBalances")]
+	fn account_balances(&self, who: AccountId) -> Result<Vec<(CurrencyId, BalanceInfo<String>)>>;
+
+	#[rpc(name = "tokens_totalIssuance")]
+	fn total_issuance(&self, currency_id: CurrencyId) -> Result<String>;
+}
+
+/// A struct that implements the [`TokensApi`].
+pub struct Tokens<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Tokens<C, P> {
+	/// Create new `Tokens` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId> TokensApi<AccountId> for Tokens<C, Block>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TokensRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
+{
+	/*
+		$ curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d   '{
+		 "jsonrpc":"2.0",
+		  "id":1,
+		  "method":"tokens_accountBalances",
+		  "params": ["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+		}'
+		{"jsonrpc":"2.0","result":[[0,{"free":"1000000000000000","frozen":"0","reserved":"0"}]],"id":1}
+	 */
+	fn account_balances(&self, who: AccountId) -> Result<Vec<(CurrencyId, BalanceInfo<String>)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.account_balances(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query account balances.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(|balances| {
+			balances.into_iter().map(|(currency_id, info)| {
+				(currency_id, BalanceInfo {
+					free: format!("{}", info.free),
+					reserved: format!("{}", info.reserved),
+					frozen: format!("{}", info.frozen),
+				})
+			}).collect()
+		})
+	}
+
+	fn total_issuance(&self, currency_id: CurrencyId) -> Result<String> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.total_issuance(&at, currency_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query total issuance.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(|total_issuance| {
+			format!("{}", total_issuance)
+		})
+	}
+}
@@ -21,12 +21,15 @@
 //! - `MultiCurrencyExtended` - Extended `MultiCurrency` with additional helper
 //!   types and methods, like updating balance
 //! by a given signed integer amount.
+//! - `NamedMultiReservableCurrency` - Reserves kept apart by an identifier.
 //!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
 //! - `transfer` - Transfer some balance to another account.
+//! - `transfer_keep_alive` - Transfer some balance to another account, keeping
+//!   the sender alive.
 //! - `transfer_all` - Transfer all balance to another account.
 //!
 //! ### Genesis Config
@@ -54,12 +57,12 @@ use orml_traits::{
 	account::MergeAccount,
 	arithmetic::{self, Signed},
 	BalanceStatus, GetByKey, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
-	MultiReservableCurrency, OnDust,
+	MultiReservableCurrency, NamedMultiReservableCurrency, OnDust,
 };
 use sp_runtime::{
 	traits::{
 		AccountIdConversion, AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member,
-		Saturating, StaticLookup, Zero,
+		One, Saturating, StaticLookup, Zero,
 	},
 	DispatchError, DispatchResult, ModuleId, RuntimeDebug,
 };
@@ -109,6 +112,15 @@ pub struct BalanceLock<Balance> {
 	pub amount: Balance,
 }
 
+/// A balance reserved under an identifier.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReserveData<ReserveIdentifier, Balance> {
+	/// The identifier of the reserve.
+	pub id: ReserveIdentifier,
+	/// The amount reserved under `id`.
+	pub amount: Balance,
+}
+
 /// balance information for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
 pub struct AccountData<Balance> {
@@ -184,6 +196,15 @@ pub mod module {
 
 		/// Handler to burn or transfer account's dust
 		type OnDust: OnDust<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
+		/// Whether module accounts are exempt from dust removal.
+		type ExemptModuleAccounts: Get<bool>;
+
+		/// The identifier of a named reserve.
+		type ReserveIdentifier: Parameter + Member + Copy + Ord;
+
+		/// The maximum number of named reserves per account and currency.
+		type MaxReserves: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -200,6 +221,10 @@ pub mod module {
 		LiquidityRestrictions,
 		/// Account still has active reserved
 		StillHasActiveReserved,
+		/// The account has too many named reserves
+		TooManyReserves,
+		/// Transfer would reap the account of the sender
+		KeepAlive,
 	}
 
 	#[pallet::event]
@@ -250,6 +275,20 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// The named reserves of a token type under an account, sorted by
+	/// identifier.
+	#[pallet::storage]
+	#[pallet::getter(fn reserves)]
+	pub type Reserves<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::CurrencyId,
+		Vec<ReserveData<T::ReserveIdentifier, T::Balance>>,
+		ValueQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub endowed_accounts: Vec<(T::AccountId, T::CurrencyId, T::Balance)>,
@@ -324,7 +363,30 @@ pub mod module {
 			Ok(().into())
 		}
 
-		/// Transfer all remaining balance to the given account.
+		/// Same as the [`transfer`] call, but with a check that the transfer
+		/// will not kill the origin account.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub fn transfer_keep_alive(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			<Self as MultiCurrency<_>>::transfer_keep_alive(currency_id, &from, &to, amount)?;
+
+			Self::deposit_event(Event::Transferred(currency_id, from, to, amount));
+			Ok(().into())
+		}
+
+		/// Transfer all remaining transferable balance to the given account.
+		///
+		/// With `keep_alive` set, enough balance is left behind to keep the
+		/// origin account alive.
 		///
 		/// The dispatch origin for this call must be `Signed` by the
 		/// transactor.
@@ -333,10 +395,11 @@ pub mod module {
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
 			currency_id: T::CurrencyId,
+			keep_alive: bool,
 		) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
-			let balance = <Self as MultiCurrency<T::AccountId>>::free_balance(currency_id, &from);
+			let balance = Self::reducible_balance(currency_id, &from, keep_alive);
 			<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, &from, &to, balance)?;
 
 			Self::deposit_event(Event::Transferred(currency_id, from, to, balance));
@@ -346,11 +409,132 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	/// The free balance of `who` that can be moved out, leaving at least
+	/// the existential deposit behind when `keep_alive` is set.
+	pub fn reducible_balance(currency_id: T::CurrencyId, who: &T::AccountId, keep_alive: bool) -> T::Balance {
+		let account = Self::accounts(who, currency_id);
+		let mut required = account.frozen();
+		if keep_alive {
+			let ed = T::ExistentialDeposits::get(&currency_id).max(One::one());
+			required = required.max(ed.saturating_sub(account.reserved));
+		}
+		account.free.saturating_sub(required)
+	}
+
+	/// All the non-empty balances of `who`, by currency.
+	pub fn accounts_of(who: &T::AccountId) -> Vec<(T::CurrencyId, AccountData<T::Balance>)> {
+		Accounts::<T>::iter_prefix(who).collect()
+	}
+
+	/// The reserved balance of `who` not reserved under any identifier.
+	fn unnamed_reserved_balance(currency_id: T::CurrencyId, who: &T::AccountId) -> T::Balance {
+		let named = Self::reserves(who, currency_id)
+			.iter()
+			.fold(Zero::zero(), |total: T::Balance, data| total.saturating_add(data.amount));
+		Self::accounts(who, currency_id).reserved.saturating_sub(named)
+	}
+
+	/// Move up to `value` from the reserved balance of `who` to their free
+	/// balance, whatever identifiers it is reserved under. Returns the amount
+	/// that was unable to be unreserved.
+	fn do_unreserve(currency_id: T::CurrencyId, who: &T::AccountId, value: T::Balance) -> T::Balance {
+		let account = Self::accounts(who, currency_id);
+		let actual = account.reserved.min(value);
+		Self::set_reserved_balance(currency_id, who, account.reserved - actual);
+		Self::set_free_balance(currency_id, who, account.free + actual);
+		value - actual
+	}
+
+	/// Slash up to `value` from the reserved balance of `who`, whatever
+	/// identifiers it is reserved under. Returns the amount that was unable
+	/// to be slashed.
+	fn do_slash_reserved(currency_id: T::CurrencyId, who: &T::AccountId, value: T::Balance) -> T::Balance {
+		let reserved_balance = Self::accounts(who, currency_id).reserved;
+		let actual = reserved_balance.min(value);
+		Self::set_reserved_balance(currency_id, who, reserved_balance - actual);
+		<TotalIssuance<T>>::mutate(currency_id, |v| *v -= actual);
+		value - actual
+	}
+
+	/// Move up to `value` from the reserved balance of `slashed` to
+	/// `beneficiary`, whatever identifiers it is reserved under. Returns the
+	/// amount that was unable to be moved.
+	fn do_repatriate_reserved(
+		currency_id: T::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: T::Balance,
+		status: BalanceStatus,
+	) -> T::Balance {
+		let from_account = Self::accounts(slashed, currency_id);
+		let to_account = Self::accounts(beneficiary, currency_id);
+		let actual = from_account.reserved.min(value);
+		match status {
+			BalanceStatus::Free => {
+				Self::set_free_balance(currency_id, beneficiary, to_account.free + actual);
+			}
+			BalanceStatus::Reserved => {
+				Self::set_reserved_balance(currency_id, beneficiary, to_account.reserved + actual);
+			}
+		}
+		Self::set_reserved_balance(currency_id, slashed, from_account.reserved - actual);
+		value - actual
+	}
+
+	/// Add `value` to the reserve `id` of `reserves`.
+	fn add_reserve(
+		reserves: &mut Vec<ReserveData<T::ReserveIdentifier, T::Balance>>,
+		id: &T::ReserveIdentifier,
+		value: T::Balance,
+	) -> DispatchResult {
+		match reserves.binary_search_by_key(id, |data| data.id) {
+			Ok(index) => {
+				reserves[index].amount = reserves[index].amount.checked_add(&value).ok_or(Error::<T>::BalanceOverflow)?;
+			}
+			Err(index) => {
+				ensure!(
+					reserves.len() < T::MaxReserves::get() as usize,
+					Error::<T>::TooManyReserves
+				);
+				reserves.insert(index, ReserveData { id: *id, amount: value });
+			}
+		}
+		Ok(())
+	}
+
+	/// Take up to `value` from the reserve `id` of `reserves`, `apply`
+	/// returning the amount actually moved out of it. Returns the amount of
+	/// `value` that was not taken.
+	fn remove_reserve(
+		reserves: &mut Vec<ReserveData<T::ReserveIdentifier, T::Balance>>,
+		id: &T::ReserveIdentifier,
+		value: T::Balance,
+		apply: impl FnOnce(T::Balance) -> T::Balance,
+	) -> T::Balance {
+		match reserves.binary_search_by_key(id, |data| data.id) {
+			Ok(index) => {
+				let to_change = reserves[index].amount.min(value);
+				let actual = apply(to_change);
+				reserves[index].amount -= actual;
+				if reserves[index].amount.is_zero() {
+					reserves.remove(index);
+				}
+				value - actual
+			}
+			Err(_) => value,
+		}
+	}
+
 	/// Check whether account_id is a module account
 	pub(crate) fn is_module_account_id(account_id: &T::AccountId) -> bool {
 		ModuleId::try_from_account(account_id).is_some()
 	}
 
+	/// Check whether the dust of account_id should be kept
+	pub(crate) fn is_dust_exempt(account_id: &T::AccountId) -> bool {
+		T::ExemptModuleAccounts::get() && Self::is_module_account_id(account_id)
+	}
+
 	pub(crate) fn try_mutate_account<R, E>(
 		who: &T::AccountId,
 		currency_id: T::CurrencyId,
@@ -365,9 +549,9 @@ impl<T: Config> Pallet<T> {
 				*maybe_account = if total.is_zero() {
 					None
 				} else {
-					// if non_zero total is below existential deposit and the account is not a
-					// module account, should handle the dust.
-					if total < T::ExistentialDeposits::get(&currency_id) && !Self::is_module_account_id(who) {
+					// if non_zero total is below existential deposit and the account is not an
+					// exempt module account, should handle the dust.
+					if total < T::ExistentialDeposits::get(&currency_id) && !Self::is_dust_exempt(who) {
 						handle_dust = Some(total);
 					}
 					Some(account)
@@ -529,6 +713,29 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
+	/// Transfer some free balance from `from` to `to`, failing with
+	/// `KeepAlive` if the total balance of `from` would drop below the
+	/// existential deposit.
+	fn transfer_keep_alive(
+		currency_id: Self::CurrencyId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() || from == to {
+			return Ok(());
+		}
+		let remaining = Self::accounts(from, currency_id)
+			.total()
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::BalanceTooLow)?;
+		ensure!(
+			remaining >= T::ExistentialDeposits::get(&currency_id).max(One::one()),
+			Error::<T>::KeepAlive
+		);
+		<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, from, to, amount)
+	}
+
 	/// Deposit some `amount` into the free balance of account `who`.
 	///
 	/// Is a no-op if the `amount` to be deposited is zero.
@@ -720,17 +927,17 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 	/// Slash from reserved balance, returning any amount that was unable to
 	/// be slashed.
 	///
+	/// Only the balance not reserved under an identifier is slashed, see
+	/// `slash_reserved_named` for the rest.
+	///
 	/// Is a no-op if the value to be slashed is zero.
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
 		if value.is_zero() {
 			return value;
 		}
 
-		let reserved_balance = Self::reserved_balance(currency_id, who);
-		let actual = reserved_balance.min(value);
-		Self::set_reserved_balance(currency_id, who, reserved_balance - actual);
-		<TotalIssuance<T>>::mutate(currency_id, |v| *v -= actual);
-		value - actual
+		let actual = Self::unnamed_reserved_balance(currency_id, who).min(value);
+		value - (actual - Self::do_slash_reserved(currency_id, who, actual))
 	}
 
 	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
@@ -758,23 +965,25 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 	/// Unreserve some funds, returning any amount that was unable to be
 	/// unreserved.
 	///
+	/// Only the balance not reserved under an identifier is released, see
+	/// `unreserve_named` for the rest.
+	///
 	/// Is a no-op if the value to be unreserved is zero.
 	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
 		if value.is_zero() {
 			return value;
 		}
 
-		let account = Self::accounts(who, currency_id);
-		let actual = account.reserved.min(value);
-		Self::set_reserved_balance(currency_id, who, account.reserved - actual);
-		Self::set_free_balance(currency_id, who, account.free + actual);
-
-		value - actual
+		let actual = Self::unnamed_reserved_balance(currency_id, who).min(value);
+		value - (actual - Self::do_unreserve(currency_id, who, actual))
 	}
 
 	/// Move the reserved balance of one account into the balance of
 	/// another, according to `status`.
 	///
+	/// Only the balance not reserved under an identifier is moved, see
+	/// `repatriate_reserved_named` for the rest.
+	///
 	/// Is a no-op if:
 	/// - the value to be moved is zero; or
 	/// - the `slashed` id equal to `beneficiary` and the `status` is
@@ -793,22 +1002,133 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		if slashed == beneficiary {
 			return match status {
 				BalanceStatus::Free => Ok(Self::unreserve(currency_id, slashed, value)),
-				BalanceStatus::Reserved => Ok(value.saturating_sub(Self::reserved_balance(currency_id, slashed))),
+				BalanceStatus::Reserved => {
+					Ok(value.saturating_sub(Self::unnamed_reserved_balance(currency_id, slashed)))
+				}
 			};
 		}
 
-		let from_account = Self::accounts(slashed, currency_id);
-		let to_account = Self::accounts(beneficiary, currency_id);
-		let actual = from_account.reserved.min(value);
-		match status {
-			BalanceStatus::Free => {
-				Self::set_free_balance(currency_id, beneficiary, to_account.free + actual);
-			}
-			BalanceStatus::Reserved => {
-				Self::set_reserved_balance(currency_id, beneficiary, to_account.reserved + actual);
-			}
+		let actual = Self::unnamed_reserved_balance(currency_id, slashed).min(value);
+		let remaining = Self::do_repatriate_reserved(currency_id, slashed, beneficiary, actual, status);
+		Ok(value - (actual - remaining))
+	}
+}
+
+impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
+	type ReserveIdentifier = T::ReserveIdentifier;
+
+	fn reserved_balance_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+	) -> Self::Balance {
+		let reserves = Self::reserves(who, currency_id);
+		reserves
+			.binary_search_by_key(id, |data| data.id)
+			.map(|index| reserves[index].amount)
+			.unwrap_or_else(|_| Zero::zero())
+	}
+
+	/// Move `value` from the free balance from `who` to their balance
+	/// reserved under `id`.
+	///
+	/// Is a no-op if value to be reserved is zero.
+	fn reserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> DispatchResult {
+		if value.is_zero() {
+			return Ok(());
 		}
-		Self::set_reserved_balance(currency_id, slashed, from_account.reserved - actual);
+
+		Reserves::<T>::try_mutate(who, currency_id, |reserves| -> DispatchResult {
+			Self::add_reserve(reserves, id, value)?;
+			<Self as MultiReservableCurrency<_>>::reserve(currency_id, who, value)
+		})
+	}
+
+	/// Unreserve some funds reserved under `id`, returning any amount that
+	/// was unable to be unreserved.
+	///
+	/// Is a no-op if the value to be unreserved is zero.
+	fn unreserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::Balance {
+		if value.is_zero() {
+			return value;
+		}
+
+		Reserves::<T>::mutate(who, currency_id, |reserves| {
+			Self::remove_reserve(reserves, id, value, |to_change| {
+				to_change - Self::do_unreserve(currency_id, who, to_change)
+			})
+		})
+	}
+
+	/// Slash from the balance reserved under `id`, returning any amount that
+	/// was unable to be slashed.
+	///
+	/// Is a no-op if the value to be slashed is zero.
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::Balance {
+		if value.is_zero() {
+			return value;
+		}
+
+		Reserves::<T>::mutate(who, currency_id, |reserves| {
+			Self::remove_reserve(reserves, id, value, |to_change| {
+				to_change - Self::do_slash_reserved(currency_id, who, to_change)
+			})
+		})
+	}
+
+	/// Move the balance of `slashed` reserved under `id` to `beneficiary`,
+	/// reserving it under the same `id` if `status` is `Reserved`.
+	///
+	/// Is a no-op if the value to be moved is zero.
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> sp_std::result::Result<Self::Balance, DispatchError> {
+		if value.is_zero() {
+			return Ok(value);
+		}
+
+		if slashed == beneficiary {
+			return match status {
+				BalanceStatus::Free => Ok(Self::unreserve_named(id, currency_id, slashed, value)),
+				BalanceStatus::Reserved => {
+					Ok(value.saturating_sub(Self::reserved_balance_named(id, currency_id, slashed)))
+				}
+			};
+		}
+
+		let to_change = Self::reserved_balance_named(id, currency_id, slashed).min(value);
+		if status == BalanceStatus::Reserved {
+			// fail before moving any funds if `beneficiary` can not hold another named reserve.
+			Self::add_reserve(&mut Self::reserves(beneficiary, currency_id), id, to_change)?;
+		}
+		let remaining = Self::do_repatriate_reserved(currency_id, slashed, beneficiary, to_change, status);
+		let actual = to_change - remaining;
+		if status == BalanceStatus::Reserved {
+			Reserves::<T>::try_mutate(beneficiary, currency_id, |reserves| Self::add_reserve(reserves, id, actual))?;
+		}
+		Reserves::<T>::mutate(slashed, currency_id, |reserves| {
+			Self::remove_reserve(reserves, id, actual, |to_change| to_change)
+		});
 		Ok(value - actual)
 	}
 }
@@ -883,9 +1203,16 @@ where
 		source: &T::AccountId,
 		dest: &T::AccountId,
 		value: Self::Balance,
-		_existence_requirement: ExistenceRequirement,
+		existence_requirement: ExistenceRequirement,
 	) -> DispatchResult {
-		<Pallet<T> as MultiCurrency<T::AccountId>>::transfer(GetCurrencyId::get(), &source, &dest, value)
+		match existence_requirement {
+			ExistenceRequirement::KeepAlive => {
+				<Pallet<T> as MultiCurrency<T::AccountId>>::transfer_keep_alive(GetCurrencyId::get(), &source, &dest, value)
+			}
+			ExistenceRequirement::AllowDeath => {
+				<Pallet<T> as MultiCurrency<T::AccountId>>::transfer(GetCurrencyId::get(), &source, &dest, value)
+			}
+		}
 	}
 
 	fn slash(who: &T::AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
@@ -15,6 +15,7 @@ use sp_std::cell::RefCell;
 pub type AccountId = AccountId32;
 pub type CurrencyId = u32;
 pub type Balance = u64;
+pub type ReserveIdentifier = [u8; 8];
 
 pub const DOT: CurrencyId = 1;
 pub const BTC: CurrencyId = 2;
@@ -24,6 +25,9 @@ pub const BOB: AccountId = AccountId32::new([1u8; 32]);
 pub const TREASURY_ACCOUNT: AccountId = AccountId32::new([2u8; 32]);
 pub const ID_1: LockIdentifier = *b"1       ";
 pub const ID_2: LockIdentifier = *b"2       ";
+pub const RID_1: ReserveIdentifier = [1u8; 8];
+pub const RID_2: ReserveIdentifier = [2u8; 8];
+pub const RID_3: ReserveIdentifier = [3u8; 8];
 
 use crate as tokens;
 
@@ -204,6 +208,8 @@ parameter_type_with_key! {
 
 parameter_types! {
 	pub DustAccount: AccountId = ModuleId(*b"orml/dst").into_account();
+	pub static ExemptModuleAccounts: bool = true;
+	pub const MaxReserves: u32 = 2;
 }
 
 impl Config for Runtime {
@@ -214,6 +220,9 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type OnDust = TransferDust<Runtime, DustAccount>;
+	type ExemptModuleAccounts = ExemptModuleAccounts;
+	type ReserveIdentifier = ReserveIdentifier;
+	type MaxReserves = MaxReserves;
 }
 pub type TreasuryCurrencyAdapter = <Runtime as pallet_treasury::Config>::Currency;
 
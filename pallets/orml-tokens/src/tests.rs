@@ -25,6 +25,29 @@ fn is_module_account_id_work() {
 	});
 }
 
+#[test]
+fn accounts_of_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Tokens::accounts_of(&ALICE), vec![]);
+
+		assert_ok!(Tokens::deposit(DOT, &ALICE, 100));
+		assert_ok!(Tokens::deposit(BTC, &ALICE, 50));
+		assert_ok!(Tokens::reserve(BTC, &ALICE, 20));
+		assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 30));
+
+		let mut accounts = Tokens::accounts_of(&ALICE);
+		accounts.sort_by_key(|(currency_id, _)| *currency_id);
+		assert_eq!(
+			accounts,
+			vec![
+				(DOT, AccountData { free: 100, reserved: 0, frozen: 30 }),
+				(BTC, AccountData { free: 30, reserved: 20, frozen: 0 }),
+			]
+		);
+		assert_eq!(Tokens::accounts_of(&BOB), vec![]);
+	});
+}
+
 #[test]
 fn remove_dust_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -67,6 +90,32 @@ fn remove_dust_work() {
 	});
 }
 
+#[test]
+fn remove_dust_of_module_account_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let module_account: AccountId = ModuleId(*b"orml/tst").into_account();
+
+		// module account is exempt from dust removal by default
+		assert_ok!(Tokens::deposit(DOT, &module_account, 1));
+		assert_eq!(Tokens::free_balance(DOT, &module_account), 1);
+		assert_eq!(Tokens::free_balance(DOT, &DustAccount::get()), 0);
+
+		ExemptModuleAccounts::set(false);
+		assert_eq!(Tokens::is_dust_exempt(&module_account), false);
+		assert_ok!(Tokens::deposit(DOT, &module_account, 2));
+		assert_ok!(Tokens::withdraw(DOT, &module_account, 2));
+
+		// dust of module account is transferred when not exempt
+		assert_eq!(Accounts::<Runtime>::contains_key(&module_account, DOT), false);
+		assert_eq!(Tokens::free_balance(DOT, &module_account), 0);
+		assert_eq!(Tokens::free_balance(DOT, &DustAccount::get()), 1);
+
+		let dust_lost_event = Event::tokens(crate::Event::DustLost(module_account, DOT, 1));
+		assert!(System::events().iter().any(|record| record.event == dust_lost_event));
+	});
+}
+
 #[test]
 fn set_lock_should_work() {
 	ExtBuilder::default()
@@ -258,6 +307,183 @@ fn repatriate_reserved_should_work() {
 		});
 }
 
+#[test]
+fn reserve_named_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_noop!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 101), Error::<Runtime>::BalanceTooLow);
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 0));
+			assert_eq!(Tokens::reserves(ALICE, DOT), vec![]);
+
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 10));
+			assert_ok!(Tokens::reserve_named(&RID_2, DOT, &ALICE, 20));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 5));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 35);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 65);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 35);
+			assert_eq!(Tokens::reserved_balance_named(&RID_2, DOT, &ALICE), 20);
+			assert_eq!(Tokens::reserved_balance_named(&RID_3, DOT, &ALICE), 0);
+			assert_eq!(
+				Tokens::reserves(ALICE, DOT),
+				vec![ReserveData { id: RID_1, amount: 35 }, ReserveData { id: RID_2, amount: 20 }]
+			);
+
+			assert_noop!(Tokens::reserve_named(&RID_3, DOT, &ALICE, 1), Error::<Runtime>::TooManyReserves);
+		});
+}
+
+#[test]
+fn unreserve_named_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 10));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+			assert_ok!(Tokens::reserve_named(&RID_2, DOT, &ALICE, 20));
+
+			assert_eq!(Tokens::unreserve_named(&RID_3, DOT, &ALICE, 10), 10);
+			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 0), 0);
+			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 20), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 60);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 10);
+
+			// never releases the reserves made under other identifiers.
+			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 50), 40);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 70);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 0);
+			assert_eq!(Tokens::reserved_balance_named(&RID_2, DOT, &ALICE), 20);
+			assert_eq!(Tokens::reserves(ALICE, DOT), vec![ReserveData { id: RID_2, amount: 20 }]);
+		});
+}
+
+#[test]
+fn unreserve_should_keep_named_reserves() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 10));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+
+			assert_eq!(Tokens::unreserve(DOT, &ALICE, 50), 40);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 70);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 30);
+			assert_eq!(Tokens::unreserve(DOT, &ALICE, 10), 10);
+
+			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 30), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+		});
+}
+
+#[test]
+fn slash_reserved_named_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 10));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+
+			assert_eq!(Tokens::slash_reserved_named(&RID_2, DOT, &ALICE, 10), 10);
+			assert_eq!(Tokens::slash_reserved_named(&RID_1, DOT, &ALICE, 50), 20);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 60);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 10);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 0);
+			assert_eq!(Tokens::total_issuance(DOT), 170);
+		});
+}
+
+#[test]
+fn repatriate_reserved_named_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &BOB, 10));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &BOB, 50));
+
+			assert_eq!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &BOB, &BOB, 60, BalanceStatus::Reserved),
+				Ok(10)
+			);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 50);
+
+			assert_eq!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &BOB, &ALICE, 30, BalanceStatus::Reserved),
+				Ok(0)
+			);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 30);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 20);
+			assert_eq!(Tokens::reserved_balance(DOT, &BOB), 30);
+
+			assert_eq!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &BOB, &ALICE, 30, BalanceStatus::Free),
+				Ok(10)
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 120);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 0);
+			assert_eq!(Tokens::reserved_balance(DOT, &BOB), 10);
+		});
+}
+
+#[test]
+fn slash_and_repatriate_reserved_should_keep_named_reserves() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 10));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+
+			assert_eq!(Tokens::slash_reserved(DOT, &ALICE, 15), 10);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 30);
+			assert_eq!(Tokens::total_issuance(DOT), 190);
+
+			assert_eq!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 10, BalanceStatus::Free),
+				Ok(10)
+			);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 100);
+
+			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 30), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 90);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+		});
+}
+
+#[test]
+fn repatriate_reserved_named_should_be_atomic() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve_named(&RID_2, DOT, &ALICE, 10));
+			assert_ok!(Tokens::reserve_named(&RID_3, DOT, &ALICE, 10));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &BOB, 50));
+
+			assert_noop!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &BOB, &ALICE, 30, BalanceStatus::Reserved),
+				Error::<Runtime>::TooManyReserves,
+			);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 20);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 50);
+
+			assert_eq!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &BOB, &ALICE, 30, BalanceStatus::Free),
+				Ok(0)
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 110);
+		});
+}
+
 #[test]
 fn slash_draw_reserved_correct() {
 	ExtBuilder::default()
@@ -324,12 +550,51 @@ fn transfer_all_should_work() {
 		.execute_with(|| {
 			System::set_block_number(1);
 
-			assert_ok!(Tokens::transfer_all(Some(ALICE).into(), BOB, DOT));
+			assert_ok!(Tokens::transfer_all(Some(ALICE).into(), BOB, DOT, false));
 			assert_eq!(Tokens::free_balance(DOT, &ALICE), 0);
 			assert_eq!(Tokens::free_balance(DOT, &BOB), 200);
 
 			let transferred_event = Event::tokens(crate::Event::Transferred(DOT, ALICE, BOB, 100));
 			assert!(System::events().iter().any(|record| record.event == transferred_event));
+
+			assert_ok!(Tokens::transfer_all(Some(BOB).into(), ALICE, DOT, true));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 198);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 2);
+
+			let transferred_event = Event::tokens(crate::Event::Transferred(DOT, BOB, ALICE, 198));
+			assert!(System::events().iter().any(|record| record.event == transferred_event));
+		});
+}
+
+#[test]
+fn transfer_keep_alive_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				Tokens::transfer_keep_alive(Some(ALICE).into(), BOB, DOT, 99),
+				Error::<Runtime>::KeepAlive,
+			);
+			assert_noop!(
+				Tokens::transfer_keep_alive(Some(ALICE).into(), BOB, DOT, 101),
+				Error::<Runtime>::BalanceTooLow,
+			);
+
+			assert_ok!(Tokens::transfer_keep_alive(Some(ALICE).into(), BOB, DOT, 98));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 2);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 198);
+
+			let transferred_event = Event::tokens(crate::Event::Transferred(DOT, ALICE, BOB, 98));
+			assert!(System::events().iter().any(|record| record.event == transferred_event));
+
+			// reserved balance keeps the account alive as well
+			assert_ok!(Tokens::reserve(DOT, &BOB, 10));
+			assert_ok!(Tokens::transfer_keep_alive(Some(BOB).into(), ALICE, DOT, 188));
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 0);
+			assert_eq!(Tokens::reserved_balance(DOT, &BOB), 10);
 		});
 }
 
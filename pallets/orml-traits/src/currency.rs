@@ -48,6 +48,15 @@ pub trait MultiCurrency<AccountId> {
 		amount: Self::Balance,
 	) -> DispatchResult;
 
+	/// Transfer some amount from one account to another, failing if the
+	/// account of `from` would be reaped.
+	fn transfer_keep_alive(
+		currency_id: Self::CurrencyId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+
 	/// Add `amount` to the balance of `who` under `currency_id` and increase
 	/// total issuance.
 	fn deposit(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
@@ -183,6 +192,66 @@ pub trait MultiReservableCurrency<AccountId>: MultiCurrency<AccountId> {
 	) -> result::Result<Self::Balance, DispatchError>;
 }
 
+/// A fungible multi-currency system where funds can be reserved under an
+/// identifier, so that they can only be released by the same reserver.
+pub trait NamedMultiReservableCurrency<AccountId>: MultiReservableCurrency<AccountId> {
+	/// The identifier of a named reserve.
+	type ReserveIdentifier: Parameter + Member + FullCodec + Copy + Ord;
+
+	/// The amount of the balance of `who` reserved under `id`.
+	fn reserved_balance_named(id: &Self::ReserveIdentifier, currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance;
+
+	/// Moves `value` from balance to reserved balance under `id`.
+	///
+	/// If the free balance is lower than `value`, then no funds will be moved
+	/// and an `Err` will be returned.
+	fn reserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> DispatchResult;
+
+	/// Moves up to `value` from the balance reserved under `id` to free
+	/// balance. This function cannot fail.
+	///
+	/// Reserves made under other identifiers are left untouched. If less
+	/// than `value` is reserved under `id`, the remaining amount is returned.
+	fn unreserve_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> Self::Balance;
+
+	/// Deducts up to `value` from the balance reserved under `id`. This
+	/// function cannot fail.
+	///
+	/// If less than `value` is reserved under `id`, the remaining amount is
+	/// returned.
+	fn slash_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> Self::Balance;
+
+	/// Moves up to `value` from the balance of `slashed` reserved under `id`
+	/// to the balance of `beneficiary`, either free or reserved under the same
+	/// `id` depending on the `status`.
+	///
+	/// As much funds up to `value` will be deducted as possible. If this is
+	/// less than `value`, then `Ok(non_zero)` will be returned.
+	fn repatriate_reserved_named(
+		id: &Self::ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError>;
+}
+
 /// Abstraction over a fungible (single) currency system.
 pub trait BasicCurrency<AccountId> {
 	/// The balance of an account.
@@ -211,6 +280,10 @@ pub trait BasicCurrency<AccountId> {
 	/// Transfer some amount from one account to another.
 	fn transfer(from: &AccountId, to: &AccountId, amount: Self::Balance) -> DispatchResult;
 
+	/// Transfer some amount from one account to another, failing if the
+	/// account of `from` would be reaped.
+	fn transfer_keep_alive(from: &AccountId, to: &AccountId, amount: Self::Balance) -> DispatchResult;
+
 	/// Add `amount` to the balance of `who` and increase total issuance.
 	fn deposit(who: &AccountId, amount: Self::Balance) -> DispatchResult;
 
@@ -1,9 +1,9 @@
 use codec::FullCodec;
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize},
-	DispatchResult,
+	DispatchError, DispatchResult,
 };
-use sp_std::fmt::Debug;
+use sp_std::{fmt::Debug, vec::Vec};
 
 /// Abstraction over a non-fungible token system.
 #[allow(clippy::upper_case_acronyms)]
@@ -26,4 +26,17 @@ pub trait NFT<AccountId> {
 
 	/// Transfer the given token ID from one account to another.
 	fn transfer(from: &AccountId, to: &AccountId, token: (Self::ClassId, Self::TokenId)) -> DispatchResult;
+
+	/// The owner of the given class ID. Returns `None` if the class does not
+	/// exist.
+	fn class_owner(class_id: Self::ClassId) -> Option<AccountId>;
+
+	/// Mint a token of `class_id` to `to` on behalf of `class_owner`, the owner
+	/// of the class. Returns the ID of the new token.
+	fn mint(
+		class_owner: &AccountId,
+		to: &AccountId,
+		class_id: Self::ClassId,
+		metadata: Vec<u8>,
+	) -> Result<Self::TokenId, DispatchError>;
 }
@@ -44,3 +44,6 @@ pub type TokenId = u64;
 /// NFT category ID type.
 pub type CategoryId = u32;
 
+
+/// Identifier of a named reserve.
+pub type ReserveIdentifier = [u8; 8];